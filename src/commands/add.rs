@@ -1,8 +1,9 @@
-use crate::{data, fg_color};
-use crate::{utils, Cli};
+use crate::data::{self, Priority};
+use crate::fg_color;
+use crate::{utils, Error};
+use chrono::NaiveDate;
 use clap::builder::styling;
-use clap::error::ErrorKind;
-use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 
 #[derive(Subcommand)]
 pub enum Commands {
@@ -23,84 +24,128 @@ pub enum AddCommands {
     Task(AddTaskArgs),
 }
 
-#[derive(Args)]
+#[derive(Args, Default)]
 pub struct AddNoteArgs {
     /// The group that you will add a note to
     pub group_name: String,
     /// The text that will show in the note
     pub text: Vec<String>,
+    /// A tag to add to the note, can be used multiple times
+    #[arg(short, long = "tag", value_parser = utils::parse_tag)]
+    pub tags: Vec<String>,
 }
 
-#[derive(Args)]
+#[derive(Args, Default)]
 pub struct AddTaskArgs {
     /// The group that you will add a task to
     pub group_name: String,
     /// The text that will show in the task
     pub text: Vec<String>,
+    /// The priority of the task
+    #[arg(short, long, value_enum, default_value_t = Priority::Normal)]
+    pub priority: Priority,
+    /// The date the task is due (YYYY-MM-DD, today, tomorrow, +3d, +2w or a weekday)
+    #[arg(short, long, value_parser = utils::parse_date_arg)]
+    pub due: Option<NaiveDate>,
+    /// A tag to add to the task, can be used multiple times
+    #[arg(short, long = "tag", value_parser = utils::parse_tag)]
+    pub tags: Vec<String>,
+    /// The ID of a task in the group to add this one to as a sub-task
+    #[arg(long)]
+    pub parent: Option<usize>,
 }
 
 impl super::Command for AddNoteArgs {
-    fn run(self, file_name: &str) {
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
-        };
-
-        if !data.groups.contains_key(&self.group_name) {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::InvalidValue,
-                    format!("Specified group `{}` does not exist", self.group_name),
-                )
-                .print();
-            return;
-        } else if self.text.is_empty() {
-            let _ = Cli::command()
-                .error(ErrorKind::MissingRequiredArgument, "No text was specified")
-                .print();
-            return;
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        if self.text.is_empty() {
+            return Err(Error::Usage("No text was specified".to_string()));
         }
 
-        let mut group = data.get_group(&self.group_name).clone();
-        group.notes.push(data::Note::new(&self.text.join(" ")));
+        let text = self.text.join(" ");
+        let summary = format!("add note \"{}\" to group {}", text, self.group_name);
+        let id = utils::update_data(file_name, &summary, |data| {
+            if !data.groups.contains_key(&self.group_name) {
+                return Err(Error::Missing(format!(
+                    "Specified group `{}` does not exist",
+                    self.group_name
+                )));
+            }
+
+            let id = data.next_note_id();
+            let mut note = data::Note::new(id, &text);
+            utils::merge_tags(&mut note.tags, &self.tags);
+
+            let mut group = data.get_group(&self.group_name)?;
+            group.notes.push(note);
 
-        data.groups.insert(self.group_name.clone(), group);
-        utils::write_data(file_name, &data);
+            data.groups.insert(self.group_name.clone(), group);
+            Ok(id)
+        })?;
 
-        println!("Added note to group `{}` {}", self.group_name, fg_color!("successfully", Green));
+        println!(
+            "Added note {} to group `{}` {}",
+            id,
+            self.group_name,
+            fg_color!("successfully", Green)
+        );
+
+        Ok(())
     }
 }
 
 impl super::Command for AddTaskArgs {
-    fn run(self, file_name: &str) {
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        if self.text.is_empty() {
+            return Err(Error::Usage("No text was specified".to_string()));
+        }
+
+        let text = self.text.join(" ");
+        let summary = match self.parent {
+            Some(parent) => format!(
+                "add task \"{}\" under task {} in group {}",
+                text, parent, self.group_name
+            ),
+            None => format!("add task \"{}\" to group {}", text, self.group_name),
         };
+        let id = utils::update_data(file_name, &summary, |data| {
+            if !data.groups.contains_key(&self.group_name) {
+                return Err(Error::Missing(format!(
+                    "Specified group `{}` does not exist",
+                    self.group_name
+                )));
+            }
 
-        if !data.groups.contains_key(&self.group_name) {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::InvalidValue,
-                    format!("Specified group `{}` does not exist", self.group_name),
-                )
-                .print();
-            return;
-        } else if self.text.is_empty() {
-            let _ = Cli::command()
-                .error(ErrorKind::MissingRequiredArgument, "No text was specified")
-                .print();
-            return;
-        }
+            let id = data.next_task_id();
+            let mut task = data::Task::new(id, &text);
+            task.priority = self.priority;
+            task.due = self.due;
+            utils::merge_tags(&mut task.tags, &self.tags);
 
-        let mut group = data.get_group(&self.group_name).clone();
-        group.tasks.push(data::Task::new(&self.text.join(" ")));
+            let mut group = data.get_group(&self.group_name)?;
+            match self.parent {
+                Some(parent) => match group.find_task_mut(parent) {
+                    Some(parent) => parent.subtasks.push(task),
+                    None => {
+                        return Err(Error::Missing(format!(
+                            "No task with ID {} exists in the group",
+                            parent
+                        )));
+                    }
+                },
+                None => group.tasks.push(task),
+            }
 
-        data.groups.insert(self.group_name.clone(), group);
-        utils::write_data(file_name, &data);
+            data.groups.insert(self.group_name.clone(), group);
+            Ok(id)
+        })?;
 
         println!(
-            "Added task to group `{}` {}",
+            "Added task {} to group `{}` {}",
+            id,
             self.group_name,
             fg_color!("successfully", Green)
         );
+
+        Ok(())
     }
 }
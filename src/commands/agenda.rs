@@ -0,0 +1,142 @@
+use crate::data::Task;
+use crate::output::{self, ItemRow, TaskView};
+use crate::{fg_color, utils, Error, Format};
+use chrono::{Days, NaiveDate};
+use clap::builder::styling;
+use clap::Args;
+use serde::Serialize;
+
+#[derive(Args)]
+pub struct AgendaArgs {
+    /// Use this flag to only show tasks from watched groups
+    #[arg(short, long)]
+    pub watched: bool,
+    /// How many days ahead to show upcoming tasks for
+    #[arg(short, long, default_value_t = 7)]
+    pub days: u64,
+}
+
+/// The agenda as it appears in structured output
+#[derive(Serialize)]
+struct AgendaView {
+    overdue: Vec<TaskView>,
+    due_today: Vec<TaskView>,
+    upcoming: Vec<TaskView>,
+}
+
+/// A task that has a due date, with its group and the task it's a sub-task of
+type DueTask = (String, Option<usize>, Task);
+
+fn task_views(tasks: &[DueTask], today: NaiveDate) -> Vec<TaskView> {
+    tasks
+        .iter()
+        .map(|(group_name, parent, task)| TaskView {
+            parent: *parent,
+            ..TaskView::new(group_name, task, today)
+        })
+        .collect()
+}
+
+fn display_section(title: String, tasks: &[DueTask]) {
+    if tasks.is_empty() {
+        return;
+    }
+
+    println!("\n{}:\n", title);
+    for (group_name, _, task) in tasks {
+        println!(
+            "  [{}] {} - {}",
+            fg_color!(group_name, Yellow),
+            task.id,
+            utils::format_task(task)
+        );
+    }
+}
+
+impl super::Command for AgendaArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        super::Report::report(self, file_name, Format::Text)
+    }
+}
+
+impl super::Report for AgendaArgs {
+    fn report(self, file_name: &str, format: Format) -> Result<(), Error> {
+        let data = utils::get_data(file_name)?;
+
+        let group_names: Vec<String> = if self.watched {
+            if data.active_groups.is_empty() {
+                return Err(Error::Invalid(
+                    "No groups are being watched (Remove --watched flag to use all groups)"
+                        .to_string(),
+                ));
+            }
+            data.active_groups.clone()
+        } else {
+            data.groups.keys().cloned().collect()
+        };
+
+        let mut due_tasks: Vec<DueTask> = vec![];
+        for group_name in group_names {
+            let group = data.get_group(&group_name)?;
+            for task in group.all_tasks() {
+                if task.due.is_some() && !task.state.is_closed() {
+                    let parent = group.parent_task(task.id);
+                    due_tasks.push((group_name.clone(), parent, task.clone()));
+                }
+            }
+        }
+        due_tasks.sort_by(|(a_group, _, a), (b_group, _, b)| {
+            (a.due, a_group, a.id).cmp(&(b.due, b_group, b.id))
+        });
+
+        let today = utils::today();
+        let Some(horizon) = today.checked_add_days(Days::new(self.days)) else {
+            return Err(Error::Usage(format!(
+                "{} days ahead is too far to look",
+                self.days
+            )));
+        };
+
+        let (overdue, rest): (Vec<_>, Vec<_>) = due_tasks
+            .into_iter()
+            .partition(|(_, _, t)| t.is_overdue(today));
+        let (due_today, rest): (Vec<_>, Vec<_>) =
+            rest.into_iter().partition(|(_, _, t)| t.due == Some(today));
+        let upcoming: Vec<_> = rest
+            .into_iter()
+            .filter(|(_, _, t)| t.due.is_some_and(|due| due <= horizon))
+            .collect();
+
+        if !format.is_text() {
+            let view = AgendaView {
+                overdue: task_views(&overdue, today),
+                due_today: task_views(&due_today, today),
+                upcoming: task_views(&upcoming, today),
+            };
+            // the due date and overdue columns tell the sections apart in tables
+            let rows: Vec<ItemRow> = view
+                .overdue
+                .iter()
+                .chain(&view.due_today)
+                .chain(&view.upcoming)
+                .map(ItemRow::from)
+                .collect();
+            return output::print(format, &view, &rows);
+        }
+
+        if overdue.is_empty() && due_today.is_empty() && upcoming.is_empty() {
+            println!("Nothing is due in the next {} days", self.days);
+            return Ok(());
+        }
+
+        display_section(fg_color!("Overdue", Red), &overdue);
+        display_section(fg_color!("Due today", Yellow), &due_today);
+        display_section(
+            fg_color!(format!("Upcoming (next {} days)", self.days), Green),
+            &upcoming,
+        );
+        println!();
+
+        Ok(())
+    }
+}
@@ -0,0 +1,85 @@
+use crate::{fg_color, utils, Error};
+use clap::builder::styling;
+use clap::Args;
+
+#[derive(Args)]
+pub struct ArchiveArgs {
+    /// The name of the group that you want to archive, its sub-groups are archived with it
+    pub group_name: String,
+}
+
+#[derive(Args)]
+pub struct UnarchiveArgs {
+    /// The name of the group that you want to unarchive
+    pub group_name: String,
+}
+
+impl super::Command for ArchiveArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = format!("archive group {}", self.group_name);
+        utils::update_data(file_name, &summary, |data| {
+            let mut group = data.get_group(&self.group_name)?;
+
+            if let Some(archived) = data.archived_ancestor(&self.group_name) {
+                return Err(Error::Invalid(if archived == self.group_name {
+                    format!("Group `{}` is already archived", self.group_name)
+                } else {
+                    format!(
+                        "Group `{}` is already archived with its parent group `{}`",
+                        self.group_name, archived
+                    )
+                }));
+            }
+
+            group.archived = true;
+            data.groups.insert(self.group_name.clone(), group);
+            Ok(())
+        })?;
+
+        println!(
+            "Archived group `{}` {}",
+            fg_color!(self.group_name, Yellow),
+            fg_color!("successfully", Green)
+        );
+
+        Ok(())
+    }
+}
+
+impl super::Command for UnarchiveArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = format!("unarchive group {}", self.group_name);
+        utils::update_data(file_name, &summary, |data| {
+            let mut group = data.get_group(&self.group_name)?;
+
+            match data.archived_ancestor(&self.group_name) {
+                None => {
+                    return Err(Error::Invalid(format!(
+                        "Group `{}` is not archived",
+                        self.group_name
+                    )));
+                }
+                // the flag is on an ancestor, clearing nothing here would leave the group hidden
+                Some(archived) if archived != self.group_name => {
+                    return Err(Error::Invalid(format!(
+                        "Group `{}` is archived with its parent group `{}`, unarchive that instead",
+                        self.group_name, archived
+                    )));
+                }
+                Some(_) => {}
+            }
+
+            group.archived = false;
+            data.groups.insert(self.group_name.clone(), group);
+            Ok(())
+        })?;
+
+        println!(
+            "Unarchived group `{}` {}",
+            fg_color!(self.group_name, Yellow),
+            fg_color!("successfully", Green)
+        );
+
+        Ok(())
+    }
+}
@@ -1,23 +1,14 @@
-use crate::utils;
-use clap::{error::ErrorKind, Args, CommandFactory};
-
-use super::Cli;
+use crate::{Error, Store};
+use clap::Args;
 
 #[derive(Args)]
 pub struct CheckArgs;
 
 impl super::Command for CheckArgs {
-    fn run(self, file_name: &str) {
-        match utils::check_data(file_name) {
-            Ok(path) => println!("Found project at {:?}", path),
-            Err(e) => {
-                let _ = Cli::command()
-                    .error(
-                        ErrorKind::Io,
-                        format!("Failed to check for a project: {}", e),
-                    )
-                    .print();
-            }
-        }
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let store = Store::find(file_name)?;
+        println!("Found project at {:?}", store.path());
+
+        Ok(())
     }
 }
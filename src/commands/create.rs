@@ -1,8 +1,7 @@
 use crate::data::Group;
-use crate::{fg_color, utils, Cli};
+use crate::{fg_color, utils, Error};
 use clap::builder::styling;
-use clap::error::ErrorKind;
-use clap::{Args, CommandFactory};
+use clap::Args;
 
 #[derive(Args)]
 pub struct CreateArgs {
@@ -13,44 +12,42 @@ pub struct CreateArgs {
 }
 
 impl super::Command for CreateArgs {
-    fn run(self, file_name: &str) {
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = match &self.parent_group {
+            Some(parent) => format!("create group {} in {}", self.group_name, parent),
+            None => format!("create group {}", self.group_name),
         };
+        utils::update_data(file_name, &summary, |data| {
+            if data.groups.contains_key(&self.group_name) {
+                return Err(Error::Invalid(format!(
+                    "A group with the name `{}` already exists",
+                    self.group_name
+                )));
+            }
 
-        if data.groups.contains_key(&self.group_name) {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::InvalidValue,
-                    format!("A group with the name `{}` already exists", self.group_name),
-                )
-                .print();
-            return;
-        }
-
-        if let Some(parent_name) = self.parent_group {
-            if data.groups.contains_key(&parent_name) {
-                let mut parent = data.get_group(&parent_name);
-                parent.groups.push(self.group_name.to_string());
-                data.groups.insert(parent_name, parent);
-            } else {
-                let _ = Cli::command()
-                    .error(
-                        ErrorKind::InvalidValue,
-                        format!("Specified parent group `{}` was not found", parent_name),
-                    )
-                    .print();
-                return;
+            if let Some(parent_name) = self.parent_group {
+                if data.groups.contains_key(&parent_name) {
+                    let mut parent = data.get_group(&parent_name)?;
+                    parent.groups.push(self.group_name.to_string());
+                    data.groups.insert(parent_name, parent);
+                } else {
+                    return Err(Error::Missing(format!(
+                        "Specified parent group `{}` was not found",
+                        parent_name
+                    )));
+                }
             }
-        }
-        data.groups
-            .insert(self.group_name.to_string(), Group::new(&self.group_name));
-        utils::write_data(file_name, &data);
+            data.groups
+                .insert(self.group_name.to_string(), Group::new(&self.group_name));
+            Ok(())
+        })?;
 
         println!(
             "Added group `{}` to project {}",
             fg_color!(self.group_name, Yellow),
             fg_color!("successfully", Green)
         );
+
+        Ok(())
     }
 }
@@ -1,29 +1,21 @@
 use clap::builder::styling;
-use clap::{error::ErrorKind, Args, CommandFactory};
+use clap::Args;
+use std::collections::HashSet;
 
-use crate::{fg_color, utils, Cli};
+use crate::data::Project;
+use crate::{fg_color, utils, Error};
 
-#[derive(Args)]
+#[derive(Args, Default)]
 pub struct DeleteArgs {
     /// The name of the group that you want to delete
     pub group_names: Vec<String>,
+    #[command(flatten)]
+    pub confirmation: super::Confirmation,
 }
 
-impl super::Command for DeleteArgs {
-    fn run(self, file_name: &str) {
-        if self.group_names.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::MissingRequiredArgument,
-                    "No groups specified to delete",
-                )
-                .print();
-            return;
-        }
-
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
-        };
+impl DeleteArgs {
+    /// Returns the groups that will be deleted, the specified groups followed by their descendants
+    fn groups_to_delete(&self, data: &Project) -> Result<Vec<String>, Error> {
         let mut undefined_groups: Vec<&str> = vec![];
         for group in &self.group_names {
             if !data.groups.contains_key(group) {
@@ -32,45 +24,121 @@ impl super::Command for DeleteArgs {
         }
 
         if !undefined_groups.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::ValueValidation,
-                    format!(
-                        "Following groups are not created: {}",
-                        undefined_groups.join(", ")
-                    ),
-                )
-                .print();
-            return;
+            return Err(Error::Missing(format!(
+                "Following groups are not created: {}",
+                undefined_groups.join(", ")
+            )));
         }
 
         let mut to_delete: Vec<String> = vec![];
+        let mut seen: HashSet<String> = HashSet::new();
 
         for group in &self.group_names {
-            to_delete.push(group.to_string());
-            to_delete.append(&mut data.get_group_descendants(group));
+            let mut groups = vec![group.to_string()];
+            groups.append(&mut data.get_group_descendants(group));
+            // a specified group can be a descendant of another one
+            for group in groups {
+                if seen.insert(group.clone()) {
+                    to_delete.push(group);
+                }
+            }
         }
 
-        while !to_delete.is_empty() {
-            if data.active_groups.contains(&to_delete[0]) {
-                let index = data
-                    .active_groups
-                    .iter()
-                    .position(|g| g == to_delete[0].as_str())
-                    .expect("Group specified to be unwatched was not found in watched groups");
-                data.active_groups.remove(index);
-            }
-            data.groups.remove(&to_delete[0]);
-            data.clean();
-            to_delete.remove(0);
+        Ok(to_delete)
+    }
+
+    /// How many of the groups that will be deleted weren't specified themselves
+    fn descendant_count(&self, to_delete: &[String]) -> usize {
+        let specified: HashSet<&String> = self.group_names.iter().collect();
+        to_delete.len() - specified.len()
+    }
+
+    /// Prints every group that will be deleted and how much data is lost with them
+    fn print_plan(&self, data: &Project, to_delete: &[String]) {
+        println!("The following group(s) will be deleted:\n");
+        for group in to_delete {
+            println!("    {}", fg_color!(data.get_group_path(group), Yellow));
         }
 
-        utils::write_data(file_name, &data);
+        let groups: Vec<_> = to_delete
+            .iter()
+            .filter_map(|g| data.groups.get(g))
+            .collect();
+        let tasks = groups.iter().map(|g| g.all_tasks().len()).sum::<usize>();
+        let notes = groups.iter().map(|g| g.notes.len()).sum::<usize>();
+        let unfinished = groups
+            .iter()
+            .flat_map(|g| g.all_tasks())
+            .filter(|t| !t.state.is_closed())
+            .count();
+
+        println!(
+            "\n{} group(s) ({} descendant(s)), {} task(s) and {} note(s) will be lost",
+            to_delete.len(),
+            self.descendant_count(to_delete),
+            tasks,
+            notes
+        );
+        if unfinished > 0 {
+            println!(
+                "{} of the tasks {} not finished",
+                fg_color!(unfinished, Red),
+                if unfinished == 1 { "is" } else { "are" }
+            );
+        }
+        println!();
+    }
+}
+
+impl super::Command for DeleteArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        if self.group_names.is_empty() {
+            return Err(Error::Usage("No groups specified to delete".to_string()));
+        }
+
+        let data = utils::get_data(file_name)?;
+        let to_delete = self.groups_to_delete(&data)?;
+        self.print_plan(&data, &to_delete);
+
+        if !self
+            .confirmation
+            .proceed("Delete these groups?", "deleted")?
+        {
+            return Ok(());
+        }
+
+        utils::update_data_summarized(file_name, |data| {
+            let to_delete = self.groups_to_delete(data)?;
+
+            let groups = if self.group_names.len() == 1 {
+                "group"
+            } else {
+                "groups"
+            };
+            let mut summary = format!("delete {} {}", groups, self.group_names.join(", "));
+            let descendants = self.descendant_count(&to_delete);
+            if descendants == 1 {
+                summary.push_str(" (+1 descendant)");
+            } else if descendants > 1 {
+                summary.push_str(&format!(" (+{} descendants)", descendants));
+            }
+
+            for group in &to_delete {
+                data.active_groups.retain(|g| g != group);
+                data.groups.remove(group);
+            }
+            data.clean();
+            // tasks in other groups shouldn't wait on tasks that were deleted
+            data.drop_missing_links();
+            Ok(((), summary))
+        })?;
 
         println!(
             "Deleted group(s) {}: {}",
             fg_color!("successfully", Green),
             self.group_names.join(", ")
         );
+
+        Ok(())
     }
 }
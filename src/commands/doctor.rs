@@ -0,0 +1,59 @@
+use crate::{fg_color, utils, Error, Store};
+use clap::builder::styling;
+use clap::Args;
+
+#[derive(Args)]
+pub struct DoctorArgs {
+    /// Use this flag to repair the problems that were found
+    #[arg(long)]
+    pub fix: bool,
+}
+
+impl super::Command for DoctorArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let store = Store::find(file_name)?;
+        let _lock = store.lock(utils::LOCK_TIMEOUT)?;
+        // a normal load refuses data with problems, which is what this command is for
+        let mut data = store.load_unchecked()?;
+        let before = data.clone();
+
+        let problems = if self.fix {
+            data.repair()
+        } else {
+            data.check()
+        };
+
+        if problems.is_empty() {
+            println!("No problems found");
+            return Ok(());
+        }
+
+        for problem in &problems {
+            println!("  {}", problem);
+        }
+        println!();
+
+        if !self.fix {
+            return Err(Error::Invalid(format!(
+                "Found {} problem(s), run `pmgr doctor --fix` to repair them",
+                problems.len()
+            )));
+        }
+
+        store.save(&data)?;
+        utils::record_change(
+            &store,
+            format!("repair {} problem(s)", problems.len()),
+            before,
+            data,
+        );
+
+        println!(
+            "Repaired {} problem(s) {}",
+            problems.len(),
+            fg_color!("successfully", Green)
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,132 @@
+use crate::data::ItemKind;
+use crate::{fg_color, utils, Error};
+use clap::builder::styling;
+use clap::{Args, Parser, Subcommand};
+
+#[derive(Subcommand)]
+pub enum Commands {
+    Edit(Edit),
+}
+
+#[derive(Parser)]
+pub struct Edit {
+    #[structopt(subcommand)]
+    pub edit_commands: EditCommands,
+}
+
+#[derive(Subcommand)]
+pub enum EditCommands {
+    /// Change the text of a note
+    Note(EditNoteArgs),
+    /// Change the text of a task
+    Task(EditTaskArgs),
+}
+
+#[derive(Args, Default)]
+pub struct EditNoteArgs {
+    /// The group that the note belongs to
+    pub group_name: String,
+    /// The ID of the note that you want to edit
+    pub id: usize,
+    /// The new text of the note
+    pub text: Vec<String>,
+    /// Use this flag to change the text in $EDITOR instead
+    #[arg(short, long, conflicts_with = "text")]
+    pub editor: bool,
+}
+
+#[derive(Args, Default)]
+pub struct EditTaskArgs {
+    /// The group that the task belongs to
+    pub group_name: String,
+    /// The ID of the task that you want to edit
+    pub id: usize,
+    /// The new text of the task
+    pub text: Vec<String>,
+    /// Use this flag to change the text in $EDITOR instead
+    #[arg(short, long, conflicts_with = "text")]
+    pub editor: bool,
+}
+
+/// Replaces the text of an item, every other field of the item is kept as it is
+fn edit_item(
+    file_name: &str,
+    kind: ItemKind,
+    group_name: &str,
+    id: usize,
+    text: Vec<String>,
+    editor: bool,
+) -> Result<(), Error> {
+    let missing_group =
+        || Error::Missing(format!("Specified group `{}` does not exist", group_name));
+    let missing_item = || Error::Missing(format!("No {} with ID {} exists in the group", kind, id));
+
+    let text = if editor {
+        // the editor is opened before locking, so other commands aren't kept waiting on it
+        let data = utils::get_data(file_name)?;
+        let Some(group) = data.groups.get(group_name) else {
+            return Err(missing_group());
+        };
+        let mut group = group.clone();
+        let Some(current) = kind.text_mut(&mut group, id) else {
+            return Err(missing_item());
+        };
+
+        utils::edit_text(current)?
+    } else {
+        text.join(" ")
+    };
+
+    if text.trim().is_empty() {
+        return Err(Error::Usage("No text was specified".to_string()));
+    }
+
+    let summary = format!("edit {} {} in group {}", kind, id, group_name);
+    utils::update_data(file_name, &summary, |data| {
+        let Some(group) = data.groups.get_mut(group_name) else {
+            return Err(missing_group());
+        };
+        let Some(current) = kind.text_mut(group, id) else {
+            return Err(missing_item());
+        };
+
+        *current = text;
+        Ok(())
+    })?;
+
+    println!(
+        "{} edited {} {} in group `{}`",
+        fg_color!("Successfully", Green),
+        kind,
+        id,
+        fg_color!(group_name, Yellow)
+    );
+
+    Ok(())
+}
+
+impl super::Command for EditNoteArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        edit_item(
+            file_name,
+            ItemKind::Note,
+            &self.group_name,
+            self.id,
+            self.text,
+            self.editor,
+        )
+    }
+}
+
+impl super::Command for EditTaskArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        edit_item(
+            file_name,
+            ItemKind::Task,
+            &self.group_name,
+            self.id,
+            self.text,
+            self.editor,
+        )
+    }
+}
@@ -0,0 +1,160 @@
+use crate::data::Project;
+use crate::{fg_color, output, utils, Error, Format, Store};
+use chrono::{DateTime, Utc};
+use clap::builder::styling;
+use clap::Args;
+use serde::Serialize;
+
+#[derive(Args)]
+pub struct UndoArgs;
+
+#[derive(Args)]
+pub struct RedoArgs;
+
+#[derive(Args)]
+pub struct HistoryArgs;
+
+/// A change in the history as it appears in structured output
+#[derive(Serialize)]
+struct EntryView<'a> {
+    number: usize,
+    summary: &'a str,
+    at: DateTime<Utc>,
+    undone: bool,
+}
+
+#[derive(Serialize)]
+struct HistoryView<'a> {
+    entries: Vec<EntryView<'a>>,
+}
+
+/// Whether two snapshots are the same apart from their ID counters
+fn same_items(a: &Project, b: &Project) -> bool {
+    let mut b = b.clone();
+    b.last_task_id = a.last_task_id;
+    b.last_note_id = a.last_note_id;
+    *a == b
+}
+
+/// Undoes the last applied change or redoes the first undone one, returns its summary
+///
+/// The ID counters are never restored to lower values, so IDs aren't given out twice.
+fn step(file_name: &str, undo: bool) -> Result<String, Error> {
+    let store = Store::find(file_name)?;
+    let _lock = store.lock(utils::LOCK_TIMEOUT)?;
+    let data = store.load_unchecked()?;
+    let mut history = store.load_history()?;
+
+    let entry = if undo {
+        let Some(entry) = history.last_applied() else {
+            return Err(Error::Invalid("There is nothing to undo".to_string()));
+        };
+        entry
+    } else {
+        let Some(entry) = history.next_undone() else {
+            return Err(Error::Invalid("There is nothing to redo".to_string()));
+        };
+        entry
+    };
+
+    // restoring a snapshot over changes that aren't in the history would lose them
+    let unchanged = history
+        .head
+        .as_ref()
+        .is_some_and(|head| same_items(head, &data));
+    if !unchanged {
+        return Err(Error::Invalid(format!(
+            "The project was changed outside of pmgr since `{}`, so it can't be {}",
+            entry.summary,
+            if undo { "undone" } else { "redone" }
+        )));
+    }
+
+    let summary = entry.summary.clone();
+    let mut restored = entry.snapshot.clone();
+    restored.last_task_id = restored.last_task_id.max(data.last_task_id);
+    restored.last_note_id = restored.last_note_id.max(data.last_note_id);
+    store.save(&restored)?;
+    history.step(undo, restored);
+    store.save_history(&history)?;
+
+    Ok(summary)
+}
+
+impl super::Command for UndoArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = step(file_name, true)?;
+
+        println!("Undid `{}` {}", summary, fg_color!("successfully", Green));
+
+        Ok(())
+    }
+}
+
+impl super::Command for RedoArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = step(file_name, false)?;
+
+        println!("Redid `{}` {}", summary, fg_color!("successfully", Green));
+
+        Ok(())
+    }
+}
+
+impl super::Command for HistoryArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        super::Report::report(self, file_name, Format::Text)
+    }
+}
+
+impl super::Report for HistoryArgs {
+    fn report(self, file_name: &str, format: Format) -> Result<(), Error> {
+        let history = Store::find(file_name)?.load_history()?;
+
+        // the newest change comes first, like `git log`
+        let entries: Vec<EntryView> = history
+            .entries
+            .iter()
+            .enumerate()
+            .rev()
+            .map(|(i, entry)| EntryView {
+                number: i + 1,
+                summary: &entry.summary,
+                at: entry.at,
+                undone: i >= history.position,
+            })
+            .collect();
+
+        if !format.is_text() {
+            let view = HistoryView { entries };
+            return output::print(format, &view, &view.entries);
+        }
+
+        if entries.is_empty() {
+            println!("No changes were made yet");
+            return Ok(());
+        }
+
+        println!();
+        for entry in entries {
+            let line = format!(
+                "  {:>3}  {}  {}",
+                entry.number,
+                utils::format_time(entry.at),
+                entry.summary
+            );
+            if entry.undone {
+                println!(
+                    "{} {}",
+                    fg_color!(line, BrightBlack),
+                    fg_color!("(undone)", BrightBlack)
+                );
+            } else {
+                println!("{}", line);
+            }
+        }
+        println!();
+
+        Ok(())
+    }
+}
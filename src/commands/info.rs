@@ -1,14 +1,16 @@
-use crate::utils;
+use crate::{output, utils, Error, Format};
 use clap::Args;
 
 #[derive(Args)]
 pub struct InfoArgs;
 
-impl super::Command for InfoArgs {
-    fn run(self, file_name: &str) {
-        let Some(data) = utils::get_data(file_name) else {
-            return;
-        };
+impl super::Report for InfoArgs {
+    fn report(self, file_name: &str, format: Format) -> Result<(), Error> {
+        let data = utils::get_data(file_name)?;
+
+        if !format.is_text() {
+            return output::print(format, &data.information, &[&data.information]);
+        }
 
         println!();
 
@@ -30,5 +32,13 @@ impl super::Command for InfoArgs {
         if !info_displayed {
             println!("No project information was set, use `pmgr set` command to add information");
         }
+
+        Ok(())
+    }
+}
+
+impl super::Command for InfoArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        super::Report::report(self, file_name, Format::Text)
     }
 }
@@ -1,9 +1,14 @@
 use crate::{
-    data::{Group, TaskState},
-    fg_color, utils, Cli,
+    data::{Note, Project, Task},
+    fg_color,
+    output::{self, ItemRow, NoteView, TaskView},
+    query, utils, Error, Format, Query,
 };
+use chrono::{DateTime, Utc};
 use clap::builder::styling;
-use clap::{error::ErrorKind, Args, CommandFactory};
+use clap::Args;
+use serde::Serialize;
+use std::cmp::Reverse;
 
 #[derive(Args)]
 pub struct ListArgs {
@@ -12,83 +17,194 @@ pub struct ListArgs {
     /// Use this flag to list all groups in the project (ignores GROUP_NAME)
     #[arg(short, long)]
     all: bool,
+    /// Use this flag to sort tasks by priority, highest first
+    #[arg(short, long)]
+    priority: bool,
+    /// Use this flag to show when tasks and notes were created and completed
+    #[arg(short, long)]
+    verbose: bool,
+    #[command(flatten)]
+    query: Query,
 }
 
-impl super::Command for ListArgs {
-    fn run(self, file_name: &str) {
-        let Some(data) = utils::get_data(file_name) else {
-            return;
-        };
-
-        let mut groups: Vec<Group> = vec![];
-
-        if self.group_name.is_some() && !self.all {
-            let group_name = &self.group_name.unwrap().clone();
-
-            if !data.groups.contains_key(group_name) {
-                let _ = Cli::command()
-                    .error(
-                        ErrorKind::InvalidValue,
-                        format!("No group with name `{}` found", group_name),
-                    )
-                    .print();
-                return;
-            }
+/// A group and its matching items as they appear in structured output
+#[derive(Serialize)]
+struct GroupView {
+    name: String,
+    archived: bool,
+    notes: Vec<NoteView>,
+    tasks: Vec<TaskView>,
+}
 
-            let group = data.get_group(group_name);
-            groups.push(group.clone());
-            if !group.groups.is_empty() {
-                let descendants = data.get_group_descendants(group_name);
-                for descendant in descendants {
-                    groups.push(data.get_group(&descendant));
-                }
-            }
-        } else if !self.all {
-            if data.active_groups.is_empty() {
-                let _ = Cli::command()
-                    .error(
-                        ErrorKind::Io,
-                        "No groups are being watched (Use --all flag to list all groups)",
-                    )
-                    .print();
-                return;
-            }
-            for group in &data.active_groups {
-                groups.push(data.get_group(group));
-            }
-        } else {
-            if data.groups.is_empty() {
-                let _ = Cli::command()
-                    .error(ErrorKind::Io, "No groups exist to list")
-                    .print();
-                return;
-            }
-            for group in data.groups.keys() {
-                groups.push(data.get_group(group));
+#[derive(Serialize)]
+struct ListView {
+    groups: Vec<GroupView>,
+}
+
+fn display_times(
+    created_at: Option<DateTime<Utc>>,
+    completed_at: Option<DateTime<Utc>>,
+    depth: usize,
+) {
+    let mut times: Vec<String> = vec![];
+    if let Some(created_at) = created_at {
+        times.push(format!("created {}", utils::format_time(created_at)));
+    }
+    if let Some(completed_at) = completed_at {
+        times.push(format!("completed {}", utils::format_time(completed_at)));
+    }
+
+    if !times.is_empty() {
+        println!(
+            "        {}{}",
+            "    ".repeat(depth),
+            fg_color!(times.join(", "), BrightBlack)
+        );
+    }
+}
+
+/// Prints a task and, indented under it, its sub-tasks
+///
+/// Unfinished tasks that wait on unfinished tasks are marked with what they're waiting on.
+fn display_task(task: &Task, depth: usize, verbose: bool, data: &Project) {
+    let counts = if task.subtasks.is_empty() {
+        String::new()
+    } else {
+        let (complete, total) = task.subtask_counts();
+        format!(" {}", fg_color!(format!("({}/{})", complete, total), BrightBlack))
+    };
+    let blockers = data.unfinished_blockers(task);
+    let waiting = if task.state.is_closed() || blockers.is_empty() {
+        String::new()
+    } else {
+        let waiting = format!("(waiting on {})", utils::format_ids(&blockers));
+        format!(" {}", fg_color!(waiting, Red))
+    };
+
+    println!(
+        "    {}{} - [{}] {}{}{}",
+        "    ".repeat(depth),
+        task.id,
+        task.state.symbol(),
+        utils::format_task(task),
+        counts,
+        waiting
+    );
+    if verbose {
+        display_times(task.created_at, task.completed_at, depth);
+    }
+
+    for subtask in &task.subtasks {
+        display_task(subtask, depth + 1, verbose, data);
+    }
+}
+
+impl ListArgs {
+    /// Tasks of a group that match the query, with sub-tasks that don't match left out
+    fn matching_tasks(&self, tasks: &[Task]) -> Vec<Task> {
+        let mut tasks: Vec<Task> = tasks.iter().filter_map(|t| self.query.prune(t)).collect();
+        if self.priority {
+            tasks.sort_by_key(|t| Reverse(t.priority));
+        }
+        tasks
+    }
+
+    /// Prints the matching items of the groups in a structured format
+    fn print(
+        &self,
+        groups: &[String],
+        data: &Project,
+        format: Format,
+    ) -> Result<(), Error> {
+        let today = utils::today();
+        let mut view = ListView { groups: vec![] };
+        let mut rows: Vec<ItemRow> = vec![];
+
+        for group in groups {
+            let group = data.get_group(group)?;
+            let notes: Vec<NoteView> = group
+                .notes
+                .iter()
+                .filter(|n| self.query.matches_note(n))
+                .map(|n| NoteView::new(&group.name, n))
+                .collect();
+            let tasks: Vec<TaskView> = self
+                .matching_tasks(&group.tasks)
+                .iter()
+                .map(|t| TaskView::new(&group.name, t, today))
+                .collect();
+
+            if self.query.is_active() && notes.is_empty() && tasks.is_empty() {
+                continue;
             }
+
+            rows.extend(notes.iter().map(ItemRow::from));
+            rows.extend(tasks.iter().flat_map(ItemRow::task_rows));
+            view.groups.push(GroupView {
+                archived: data.is_archived(&group.name),
+                name: group.name,
+                notes,
+                tasks,
+            });
         }
 
+        output::print(format, &view, &rows)
+    }
+}
+
+impl super::Command for ListArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        super::Report::report(self, file_name, Format::Text)
+    }
+}
+
+impl super::Report for ListArgs {
+    fn report(self, file_name: &str, format: Format) -> Result<(), Error> {
+        let data = utils::get_data(file_name)?;
+
+        let groups =
+            query::select_groups(&data, self.group_name.as_deref(), self.all, &self.query)?;
+
+        if !format.is_text() {
+            return self.print(&groups, &data, format);
+        }
+
+        let mut shown_groups = 0;
         for group in &groups {
-            println!("\n[{}]\n", fg_color!(group.name, Yellow));
-            if !group.notes.is_empty() {
+            let group = data.get_group(group)?;
+            let notes: Vec<&Note> = group
+                .notes
+                .iter()
+                .filter(|n| self.query.matches_note(n))
+                .collect();
+            let tasks = self.matching_tasks(&group.tasks);
+
+            // queries can span many groups, so only the ones with matches are shown
+            if self.query.is_active() && notes.is_empty() && tasks.is_empty() {
+                continue;
+            }
+            shown_groups += 1;
+
+            let archived = if data.is_archived(&group.name) {
+                format!(" {}", fg_color!("(archived)", BrightBlack))
+            } else {
+                String::new()
+            };
+            println!("\n[{}]{}\n", fg_color!(group.name, Yellow), archived);
+            if !notes.is_empty() {
                 println!("  Notes:");
-                let mut note_count = 0;
-                for note in &group.notes {
-                    note_count += 1;
-                    println!("    {} - {}", note_count, note.note);
+                for note in &notes {
+                    println!("    {} - {}", note.id, utils::format_note(note));
+                    if self.verbose {
+                        display_times(note.created_at, None, 0);
+                    }
                 }
                 println!();
             }
-            if !group.tasks.is_empty() {
+            if !tasks.is_empty() {
                 println!("  Tasks:");
-                let mut task_count = 0;
-                for task in &group.tasks {
-                    task_count += 1;
-                    let task_state = match task.state {
-                        TaskState::Complete => "x",
-                        TaskState::Incomplete => " ",
-                    };
-                    println!("    {} - [{}] {}", task_count, task_state, task.task);
+                for task in &tasks {
+                    display_task(task, 0, self.verbose, &data);
                 }
                 println!();
             }
@@ -96,5 +212,11 @@ impl super::Command for ListArgs {
                 println!("Group is empty...\n");
             }
         }
+
+        if shown_groups == 0 {
+            println!("Nothing matches the given filters");
+        }
+
+        Ok(())
     }
 }
@@ -0,0 +1,55 @@
+use crate::data::SCHEMA_VERSION;
+use crate::{fg_color, utils, Error, Store};
+use clap::builder::styling;
+use clap::Args;
+
+#[derive(Args)]
+pub struct MigrateArgs {
+    /// Use this flag to only show what would change, without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl super::Command for MigrateArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let store = Store::find(file_name)?;
+        let _lock = store.lock(utils::LOCK_TIMEOUT)?;
+        let (data, applied) = store.load_migrated()?;
+
+        if applied.is_empty() {
+            println!("Project is already at schema version {}", SCHEMA_VERSION);
+            return Ok(());
+        }
+
+        for migration in &applied {
+            println!(
+                "\n[{}] {}\n",
+                fg_color!(format!("v{} -> v{}", migration.from, migration.to), Yellow),
+                migration.description
+            );
+            if migration.changes.is_empty() {
+                println!("  Nothing to change");
+            }
+            for change in &migration.changes {
+                println!("  {}", change);
+            }
+        }
+        println!();
+
+        if self.dry_run {
+            println!("Dry run, nothing was written");
+            return Ok(());
+        }
+
+        // not recorded in the history, undoing it would only be upgraded again on the next load
+        store.save(&data)?;
+
+        println!(
+            "Project migrated to schema version {} {}",
+            SCHEMA_VERSION,
+            fg_color!("successfully", Green)
+        );
+
+        Ok(())
+    }
+}
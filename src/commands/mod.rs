@@ -1,6 +1,7 @@
 use std::{fs, io::{self, Write}};
 use clap::{self, Args, CommandFactory, Parser, Subcommand};
 use clap_complete::aot::{generate, Generator, Shell};
+use crate::{Error, Format};
 pub mod read;
 pub mod list;
 pub mod info;
@@ -14,6 +15,18 @@ pub mod unwatch;
 pub mod add;
 pub mod remove;
 pub mod task;
+pub mod migrate;
+pub mod agenda;
+pub mod tag;
+pub mod tags;
+pub mod search;
+pub mod edit;
+pub mod transfer;
+pub mod rename;
+pub mod reparent;
+pub mod doctor;
+pub mod history;
+pub mod archive;
 
 #[derive(Parser)]
 #[command(version, about, long_about = None)]
@@ -21,6 +34,9 @@ pub mod task;
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+    /// How read commands print their results
+    #[arg(long, global = true, value_enum, default_value_t = Format::Text)]
+    pub format: Format,
 }
 
 #[derive(Subcommand)]
@@ -41,6 +57,14 @@ pub enum Commands {
     Create(create::CreateArgs),
     /// Delete a group in the current project
     Delete(delete::DeleteArgs),
+    /// Rename a group in the current project
+    Rename(rename::RenameArgs),
+    /// Move a group under another parent group
+    Reparent(reparent::ReparentArgs),
+    /// Archive a group, hiding it and its sub-groups
+    Archive(archive::ArchiveArgs),
+    /// Unarchive a group, showing it again
+    Unarchive(archive::UnarchiveArgs),
     /// Watch a group or a list of groups
     Watch(watch::WatchArgs),
     /// Unwatch a group or a list of groups
@@ -49,14 +73,72 @@ pub enum Commands {
     Add(add::Add),
     /// Remove some data from a group
     Remove(remove::Remove),
+    /// Change the text of a task or a note
+    Edit(edit::Edit),
+    /// Move tasks or notes to another group
+    Move(transfer::Move),
+    /// Copy tasks or notes to another group
+    Copy(transfer::Copy),
     /// Do some commands to tasks
     Task(task::Task),
+    /// Add or remove tags of tasks and notes
+    Tag(tag::Tag),
+    /// Show how many tasks and notes use each tag
+    Tags(tags::TagsArgs),
+    /// Search the text of tasks and notes
+    Search(search::SearchArgs),
+    /// List overdue, due today and upcoming tasks
+    Agenda(agenda::AgendaArgs),
+    /// Undo the last change to the project
+    Undo(history::UndoArgs),
+    /// Redo the last undone change to the project
+    Redo(history::RedoArgs),
+    /// List the changes that can be undone or redone
+    History(history::HistoryArgs),
+    /// Find and repair broken links in the project data
+    Doctor(doctor::DoctorArgs),
+    /// Upgrade the project file to the current schema version
+    Migrate(migrate::MigrateArgs),
     /// Generate shell completions for pmgr
     ShellCompletions(ShellCompletionArgs),
 }
 
 pub trait Command {
-    fn run(self, file_name: &str);
+    fn run(self, file_name: &str) -> Result<(), Error>;
+}
+
+/// Commands that only read the project, their results can be printed in other formats
+pub trait Report {
+    fn report(self, file_name: &str, format: Format) -> Result<(), Error>;
+}
+
+/// Flags of commands that delete or drop data, which ask before doing it
+#[derive(Args, Clone, Copy, Default, Debug)]
+pub struct Confirmation {
+    /// Use this flag to skip the confirmation
+    #[arg(short, long)]
+    pub yes: bool,
+    /// Use this flag to only show what would change, without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+impl Confirmation {
+    /// Whether the command should go on, after the plan of what it does was printed
+    ///
+    /// `done` describes what happens, like "deleted", for the messages when it doesn't go on.
+    pub fn proceed(self, question: &str, done: &str) -> Result<bool, Error> {
+        if self.dry_run {
+            println!("Dry run, nothing was {}", done);
+            return Ok(false);
+        }
+        if self.yes || crate::utils::confirm(question)? {
+            return Ok(true);
+        }
+
+        println!("Nothing was {}", done);
+        Ok(false)
+    }
 }
 
 #[derive(Args)]
@@ -70,7 +152,7 @@ fn print_completions<G: Generator>(gen: G, cmd: &mut clap::Command, out: &mut dy
 }
 
 impl ShellCompletionArgs {
-    pub fn run(self) {
+    pub fn run(self) -> Result<(), Error> {
         let mut cmd = Cli::command();
 
         println!("Generating shell completions for {}...", self.shell);
@@ -79,12 +161,14 @@ impl ShellCompletionArgs {
             let mut file = fs::OpenOptions::new()
                 .write(true)
                 .create(true)
-                .open(path)
-                .expect("Failed to write completions to file");
+                .truncate(true)
+                .open(path)?;
 
             print_completions(self.shell, &mut cmd, &mut file);
         } else {
             print_completions(self.shell, &mut cmd, &mut io::stdout());
         }
+
+        Ok(())
     }
 }
@@ -1,11 +1,46 @@
 use clap::Args;
-use crate::utils::get_data;
+use crate::output::{self, ItemRow, NoteView, TaskView};
+use crate::{utils::{self, get_data}, Error, Format, Query};
 
 #[derive(Args)]
-pub struct ReadArgs;
+pub struct ReadArgs {
+    #[command(flatten)]
+    pub query: Query,
+}
+
+impl super::Report for ReadArgs {
+    fn report(self, file_name: &str, format: Format) -> Result<(), Error> {
+        let mut data = get_data(file_name)?;
+        let today = utils::today();
+
+        // every group is kept, archived or not, so the group tree stays whole
+        if self.query.is_active() {
+            for group in data.groups.values_mut() {
+                group.notes.retain(|n| self.query.matches_note(n));
+                group.tasks = group.tasks.iter().filter_map(|t| self.query.prune(t)).collect();
+            }
+        }
+
+        let mut group_names: Vec<&String> = data.groups.keys().collect();
+        group_names.sort();
+
+        let mut rows: Vec<ItemRow> = vec![];
+        for name in group_names {
+            let group = &data.groups[name];
+            for note in &group.notes {
+                rows.push(ItemRow::from(&NoteView::new(name, note)));
+            }
+            for task in &group.tasks {
+                rows.extend(ItemRow::task_rows(&TaskView::new(name, task, today)));
+            }
+        }
+
+        output::print(format, &data, &rows)
+    }
+}
 
 impl super::Command for ReadArgs {
-    fn run(self, file_name: &str) {
-        println!("{:?}", get_data(file_name));
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        super::Report::report(self, file_name, Format::Text)
     }
 }
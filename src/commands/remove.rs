@@ -1,7 +1,7 @@
-use crate::{fg_color, utils, Cli};
-use clap::error::ErrorKind;
-use clap::{Args, CommandFactory, Parser, Subcommand};
+use crate::data::{Group, ItemKind, Project, Task};
+use crate::{fg_color, utils, Error};
 use clap::builder::styling;
+use clap::{Args, Parser, Subcommand};
 
 #[derive(Args)]
 pub struct RemoveArgs {
@@ -28,148 +28,179 @@ pub enum RemoveCommands {
     Task(RemoveTaskArgs),
 }
 
-#[derive(Args)]
+#[derive(Args, Default)]
 pub struct RemoveNoteArgs {
     /// The group that you will remove a note from
     pub group_name: String,
     /// The ID(s) of the note(s) you want to remove
     pub ids: Vec<usize>,
+    #[command(flatten)]
+    pub confirmation: super::Confirmation,
 }
 
-#[derive(Args)]
+#[derive(Args, Default)]
 pub struct RemoveTaskArgs {
     /// The group that you will remove a task from
     pub group_name: String,
     /// The ID(s) of the task(s) you want to remove
     pub ids: Vec<usize>,
+    #[command(flatten)]
+    pub confirmation: super::Confirmation,
 }
 
-impl super::Command for RemoveNoteArgs {
-    fn run(self, file_name: &str) {
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
-        };
-
-        if !data.groups.contains_key(&self.group_name) {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::InvalidValue,
-                    format!("Specified group `{}` does not exist", self.group_name),
-                )
-                .print();
-            return;
-        } else if self.ids.is_empty() {
-            let _ = Cli::command()
-                .error(ErrorKind::MissingRequiredArgument, "No data ID specified")
-                .print();
-            return;
-        }
+/// Returns the group that items are removed from, checking that every ID is in it
+fn find_group(
+    data: &Project,
+    kind: ItemKind,
+    group_name: &str,
+    ids: &[usize],
+) -> Result<Group, Error> {
+    if !data.groups.contains_key(group_name) {
+        return Err(Error::Missing(format!(
+            "Specified group `{}` does not exist",
+            group_name
+        )));
+    } else if ids.is_empty() {
+        return Err(Error::Usage("No data ID specified".to_string()));
+    }
 
-        let mut group = data.get_group(&self.group_name).clone();
+    let group = data.get_group(group_name)?;
+    let invalid_ids: Vec<String> = ids
+        .iter()
+        .filter(|id| match kind {
+            ItemKind::Task => group.find_task(**id).is_none(),
+            ItemKind::Note => group.note_index(**id).is_none(),
+        })
+        .map(|id| id.to_string())
+        .collect();
+
+    if !invalid_ids.is_empty() {
+        return Err(Error::Missing(format!(
+            "Some given IDs do not exist in the group: {}",
+            invalid_ids.join(", ")
+        )));
+    }
 
-        let mut invalid_ids: Vec<String> = vec![];
-        let mut remove_count = 0;
+    Ok(group)
+}
 
-        for id in &self.ids {
-            if group.notes.len() < *id {
-                invalid_ids.push(id.to_string());
-            }
-        }
+impl super::Command for RemoveNoteArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let data = utils::get_data(file_name)?;
+        let group = find_group(&data, ItemKind::Note, &self.group_name, &self.ids)?;
 
-        if !invalid_ids.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::InvalidValue,
-                    format!(
-                        "Some given IDs are out of range: {}",
-                        invalid_ids.join(", ")
-                    ),
-                )
-                .print();
-            return;
+        println!(
+            "The following note(s) will be removed from group `{}`:\n",
+            fg_color!(self.group_name, Yellow)
+        );
+        for note in group.notes.iter().filter(|n| self.ids.contains(&n.id)) {
+            println!("    {} - {}", note.id, utils::format_note(note));
         }
+        println!();
 
-        for id in &self.ids {
-            group.notes.remove(id - 1 - remove_count);
-            remove_count += 1;
+        if !self
+            .confirmation
+            .proceed("Remove these notes?", "removed")?
+        {
+            return Ok(());
         }
 
-        data.groups.insert(self.group_name.clone(), group);
-        utils::write_data(file_name, &data);
+        let summary = format!(
+            "remove note(s) {} from group {}",
+            utils::format_ids(&self.ids),
+            self.group_name
+        );
+        utils::update_data(file_name, &summary, |data| {
+            let mut group = find_group(data, ItemKind::Note, &self.group_name, &self.ids)?;
+            group.notes.retain(|i| !self.ids.contains(&i.id));
 
-        let mut formatted_ids: String = self.ids.iter().map(|i| i.to_string() + ", ").collect();
-        formatted_ids.truncate(formatted_ids.len() - 2);
+            data.groups.insert(self.group_name.clone(), group);
+            Ok(())
+        })?;
 
         println!(
             "Removed note(s) from group `{}` {}: {}",
             fg_color!(self.group_name, Yellow),
             fg_color!("successfully", Green),
-            formatted_ids
+            utils::format_ids(&self.ids)
         );
+
+        Ok(())
     }
 }
 
 impl super::Command for RemoveTaskArgs {
-    fn run(self, file_name: &str) {
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
-        };
-
-        if !data.groups.contains_key(&self.group_name) {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::InvalidValue,
-                    format!("Specified group `{}` does not exist", self.group_name),
-                )
-                .print();
-            return;
-        } else if self.ids.is_empty() {
-            let _ = Cli::command()
-                .error(ErrorKind::MissingRequiredArgument, "No data ID specified")
-                .print();
-            return;
-        }
-
-        let mut group = data.get_group(&self.group_name).clone();
-
-        let mut invalid_ids: Vec<String> = vec![];
-        let mut remove_count = 0;
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let data = utils::get_data(file_name)?;
+        let group = find_group(&data, ItemKind::Task, &self.group_name, &self.ids)?;
 
-        for id in &self.ids {
-            if group.tasks.len() < *id {
-                invalid_ids.push(id.to_string());
+        println!(
+            "The following task(s) will be removed from group `{}`:\n",
+            fg_color!(self.group_name, Yellow)
+        );
+        let tasks: Vec<_> = group
+            .all_tasks()
+            .into_iter()
+            .filter(|t| self.ids.contains(&t.id))
+            .collect();
+        for task in &tasks {
+            println!(
+                "    {} - [{}] {}",
+                task.id,
+                task.state.symbol(),
+                utils::format_task(task)
+            );
+            // sub-tasks go with their parent
+            let subtasks = task.all_subtasks().len();
+            if subtasks > 0 {
+                println!("        and its {} sub-task(s)", subtasks);
             }
         }
-
-        if !invalid_ids.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::InvalidValue,
-                    format!(
-                        "Some given IDs are out of range: {}",
-                        invalid_ids.join(", ")
-                    ),
-                )
-                .print();
-            return;
+        // sub-tasks are counted once, even when they're also given on their own
+        let mut removed: Vec<&Task> = tasks
+            .iter()
+            .flat_map(|t| std::iter::once(*t).chain(t.all_subtasks()))
+            .collect();
+        removed.sort_by_key(|t| t.id);
+        removed.dedup_by_key(|t| t.id);
+        let unfinished = removed.iter().filter(|t| !t.state.is_closed()).count();
+        if unfinished > 0 {
+            println!(
+                "\n{} of them {} not finished",
+                fg_color!(unfinished, Red),
+                if unfinished == 1 { "is" } else { "are" }
+            );
         }
+        println!();
 
-        for id in &self.ids {
-            group.tasks.remove(id - 1 - remove_count);
-            remove_count += 1;
+        if !self
+            .confirmation
+            .proceed("Remove these tasks?", "removed")?
+        {
+            return Ok(());
         }
 
-        data.groups.insert(self.group_name.clone(), group);
-        utils::write_data(file_name, &data);
+        let summary = format!(
+            "remove task(s) {} from group {}",
+            utils::format_ids(&self.ids),
+            self.group_name
+        );
+        utils::update_data(file_name, &summary, |data| {
+            let mut group = find_group(data, ItemKind::Task, &self.group_name, &self.ids)?;
+            group.remove_tasks(&self.ids);
 
-        let mut formatted_ids: String = self.ids.iter().map(|i| i.to_string() + ", ").collect();
-        formatted_ids.truncate(formatted_ids.len() - 2);
+            data.groups.insert(self.group_name.clone(), group);
+            data.drop_missing_links();
+            Ok(())
+        })?;
 
         println!(
             "Removed task(s) from group `{}` {}: {}",
             fg_color!(self.group_name, Yellow),
             fg_color!("successfully", Green),
-            formatted_ids
+            utils::format_ids(&self.ids)
         );
+
+        Ok(())
     }
 }
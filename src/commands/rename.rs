@@ -0,0 +1,47 @@
+use crate::{fg_color, utils, Error};
+use clap::builder::styling;
+use clap::Args;
+
+#[derive(Args)]
+pub struct RenameArgs {
+    /// The current name of the group
+    pub old_name: String,
+    /// The new name of the group
+    pub new_name: String,
+}
+
+impl super::Command for RenameArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        if self.new_name.trim().is_empty() {
+            return Err(Error::Usage("The new group name can't be empty".to_string()));
+        }
+
+        let summary = format!("rename group {} to {}", self.old_name, self.new_name);
+        utils::update_data(file_name, &summary, |data| {
+            if !data.groups.contains_key(&self.old_name) {
+                return Err(Error::Missing(format!(
+                    "Specified group `{}` does not exist",
+                    self.old_name
+                )));
+            }
+            if data.groups.contains_key(&self.new_name) {
+                return Err(Error::Invalid(format!(
+                    "A group with the name `{}` already exists",
+                    self.new_name
+                )));
+            }
+
+            data.rename_group(&self.old_name, &self.new_name);
+            Ok(())
+        })?;
+
+        println!(
+            "Renamed group `{}` to `{}` {}",
+            fg_color!(self.old_name, Yellow),
+            fg_color!(self.new_name, Yellow),
+            fg_color!("successfully", Green)
+        );
+
+        Ok(())
+    }
+}
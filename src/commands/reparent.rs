@@ -0,0 +1,79 @@
+use crate::{fg_color, utils, Error};
+use clap::builder::styling;
+use clap::{ArgGroup, Args};
+
+#[derive(Args)]
+#[command(group(ArgGroup::new("parent").required(true).args(["new_parent", "root"])))]
+pub struct ReparentArgs {
+    /// The group that you want to move
+    pub group_name: String,
+    /// The group that will be the new parent
+    pub new_parent: Option<String>,
+    /// Use this flag to move the group to the top level of the project
+    #[arg(short, long)]
+    pub root: bool,
+}
+
+impl super::Command for ReparentArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = match &self.new_parent {
+            Some(parent) => format!("move group {} into {}", self.group_name, parent),
+            None => format!("move group {} to the top level", self.group_name),
+        };
+        utils::update_data(file_name, &summary, |data| {
+            if !data.groups.contains_key(&self.group_name) {
+                return Err(Error::Missing(format!(
+                    "Specified group `{}` does not exist",
+                    self.group_name
+                )));
+            }
+
+            let old_parent = data.get_group_parent(&self.group_name);
+            if let Some(new_parent) = &self.new_parent {
+                if !data.groups.contains_key(new_parent) {
+                    return Err(Error::Missing(format!(
+                        "Specified parent group `{}` was not found",
+                        new_parent
+                    )));
+                }
+                // a group under itself would make its descendants endless
+                if *new_parent == self.group_name
+                    || data
+                        .get_group_descendants(&self.group_name)
+                        .contains(new_parent)
+                {
+                    return Err(Error::Invalid(format!(
+                        "Group `{}` can't be moved under itself or one of its descendants",
+                        self.group_name
+                    )));
+                }
+            }
+
+            if old_parent == self.new_parent {
+                return Err(Error::Invalid(match &self.new_parent {
+                    Some(parent) => format!(
+                        "Group `{}` is already a child of `{}`",
+                        self.group_name, parent
+                    ),
+                    None => format!("Group `{}` is already at the top level", self.group_name),
+                }));
+            }
+
+            data.reparent_group(&self.group_name, self.new_parent.as_deref());
+            Ok(())
+        })?;
+
+        let parent = match &self.new_parent {
+            Some(parent) => format!("under `{}`", fg_color!(parent, Yellow)),
+            None => "to the top level".to_string(),
+        };
+        println!(
+            "Moved group `{}` {} {}",
+            fg_color!(self.group_name, Yellow),
+            parent,
+            fg_color!("successfully", Green)
+        );
+
+        Ok(())
+    }
+}
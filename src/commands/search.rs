@@ -0,0 +1,226 @@
+use crate::data::{Note, Project, Task};
+use crate::output::{self, NoteView, TaskView};
+use crate::{fg_color, utils, Error, Format};
+use clap::builder::styling;
+use clap::Args;
+use regex::{Regex, RegexBuilder};
+use serde::Serialize;
+
+#[derive(Args)]
+pub struct SearchArgs {
+    /// The text to look for in tasks and notes
+    pub pattern: String,
+    /// Use this flag to ignore case when matching
+    #[arg(short, long)]
+    pub ignore_case: bool,
+    /// Use this flag to treat PATTERN as a regular expression
+    #[arg(short, long)]
+    pub regex: bool,
+    /// Use this flag to only search watched groups
+    #[arg(short, long, conflicts_with = "group")]
+    pub watched: bool,
+    /// Only search this group and its descendants
+    #[arg(short, long)]
+    pub group: Option<String>,
+}
+
+/// A task or a note whose text matched the pattern
+enum Hit<'a> {
+    Note(&'a str, &'a Note),
+    Task(&'a str, &'a Task),
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum ItemView {
+    Task(TaskView),
+    Note(NoteView),
+}
+
+/// A match as it appears in structured output, with the full path of its group
+#[derive(Serialize)]
+struct HitView {
+    kind: &'static str,
+    path: String,
+    #[serde(flatten)]
+    item: ItemView,
+}
+
+#[derive(Serialize)]
+struct SearchView {
+    hits: Vec<HitView>,
+}
+
+/// A match as a single row of a table
+#[derive(Serialize)]
+struct HitRow {
+    kind: &'static str,
+    path: String,
+    group: String,
+    id: usize,
+    text: String,
+    state: Option<&'static str>,
+}
+
+impl SearchArgs {
+    fn matcher(&self) -> Result<Regex, Error> {
+        let pattern = if self.regex {
+            self.pattern.clone()
+        } else {
+            regex::escape(&self.pattern)
+        };
+
+        RegexBuilder::new(&pattern)
+            .case_insensitive(self.ignore_case)
+            .build()
+            .map_err(|e| Error::Usage(format!("Invalid regular expression: {}", e)))
+    }
+
+    fn groups(&self, data: &Project) -> Result<Vec<String>, Error> {
+        if let Some(group_name) = &self.group {
+            if !data.groups.contains_key(group_name) {
+                return Err(Error::Missing(format!(
+                    "Specified group `{}` does not exist",
+                    group_name
+                )));
+            }
+
+            let mut groups = vec![group_name.to_string()];
+            groups.append(&mut data.get_group_descendants(group_name));
+            return Ok(groups);
+        }
+
+        if self.watched {
+            if data.active_groups.is_empty() {
+                return Err(Error::Invalid(
+                    "No groups are being watched (Remove --watched flag to search all groups)"
+                        .to_string(),
+                ));
+            }
+            return Ok(data.active_groups.clone());
+        }
+
+        let mut groups: Vec<String> = data.groups.keys().cloned().collect();
+        groups.sort();
+        Ok(groups)
+    }
+}
+
+/// Colours the parts of `text` that match
+fn highlight(text: &str, matcher: &Regex) -> String {
+    let mut highlighted = String::new();
+    let mut last = 0;
+
+    for m in matcher.find_iter(text) {
+        highlighted.push_str(&text[last..m.start()]);
+        highlighted.push_str(&fg_color!(m.as_str(), Red));
+        last = m.end();
+    }
+    highlighted.push_str(&text[last..]);
+
+    highlighted
+}
+
+impl super::Command for SearchArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        super::Report::report(self, file_name, Format::Text)
+    }
+}
+
+impl super::Report for SearchArgs {
+    fn report(self, file_name: &str, format: Format) -> Result<(), Error> {
+        let matcher = self.matcher()?;
+        let data = utils::get_data(file_name)?;
+        let group_names = self.groups(&data)?;
+
+        let mut hits: Vec<Hit> = vec![];
+        for group_name in &group_names {
+            let group = &data.groups[group_name];
+            for note in group.notes.iter().filter(|n| matcher.is_match(&n.note)) {
+                hits.push(Hit::Note(group_name, note));
+            }
+            for task in group
+                .all_tasks()
+                .into_iter()
+                .filter(|t| matcher.is_match(&t.task))
+            {
+                hits.push(Hit::Task(group_name, task));
+            }
+        }
+
+        if !format.is_text() {
+            let today = utils::today();
+            let view = SearchView {
+                hits: hits
+                    .iter()
+                    .map(|hit| match hit {
+                        Hit::Note(group, note) => HitView {
+                            kind: "note",
+                            path: data.get_group_path(group),
+                            item: ItemView::Note(NoteView::new(group, note)),
+                        },
+                        Hit::Task(group, task) => HitView {
+                            kind: "task",
+                            path: data.get_group_path(group),
+                            item: ItemView::Task(TaskView {
+                                parent: data.groups[*group].parent_task(task.id),
+                                ..TaskView::new(group, task, today)
+                            }),
+                        },
+                    })
+                    .collect(),
+            };
+            let rows: Vec<HitRow> = view
+                .hits
+                .iter()
+                .map(|hit| {
+                    let (group, id, text, state) = match &hit.item {
+                        ItemView::Task(task) => {
+                            (&task.group, task.id, &task.text, Some(task.state))
+                        }
+                        ItemView::Note(note) => (&note.group, note.id, &note.text, None),
+                    };
+                    HitRow {
+                        kind: hit.kind,
+                        path: hit.path.clone(),
+                        group: group.clone(),
+                        id,
+                        text: text.clone(),
+                        state,
+                    }
+                })
+                .collect();
+            return output::print(format, &view, &rows);
+        }
+
+        if hits.is_empty() {
+            println!("No tasks or notes match `{}`", self.pattern);
+            return Ok(());
+        }
+
+        println!();
+        for hit in &hits {
+            let (group, kind, id, state, text) = match hit {
+                Hit::Note(group, note) => (group, "note", note.id, String::new(), &note.note),
+                Hit::Task(group, task) => (
+                    group,
+                    "task",
+                    task.id,
+                    format!("[{}] ", task.state.symbol()),
+                    &task.task,
+                ),
+            };
+            println!(
+                "  {} {} {} - {}{}",
+                fg_color!(data.get_group_path(group), Yellow),
+                kind,
+                id,
+                state,
+                highlight(text, &matcher)
+            );
+        }
+        println!();
+
+        Ok(())
+    }
+}
@@ -1,10 +1,8 @@
-use clap::{error::ErrorKind, Args, CommandFactory, Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
 use clap::builder::styling;
 use url::Url;
 
-use crate::{fg_color, utils};
-
-use super::Cli;
+use crate::{fg_color, utils, Error};
 
 #[derive(Subcommand)]
 pub enum Commands {
@@ -46,66 +44,54 @@ pub struct SetRepoArgs {
 }
 
 impl super::Command for SetTitleArgs {
-    fn run(self, file_name: &str) {
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
-        };
-
-        data.information.title = Some(self.title);
-
-        utils::write_data(file_name, &data);
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        utils::update_data(file_name, "set project title", |data| {
+            data.information.title = Some(self.title);
+            Ok(())
+        })?;
 
         println!("New project title set {}", fg_color!("successfully", Green));
+
+        Ok(())
     }
 }
 
 impl super::Command for SetDescArgs {
-    fn run(self, file_name: &str) {
+    fn run(self, file_name: &str) -> Result<(), Error> {
         if self.desc.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::MissingRequiredArgument,
-                    "No description was specified",
-                )
-                .print();
-            return;
+            return Err(Error::Usage("No description was specified".to_string()));
         }
 
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
-        };
-
-        data.information.description = Some(self.desc.join(" "));
-
-        utils::write_data(file_name, &data);
+        utils::update_data(file_name, "set project description", |data| {
+            data.information.description = Some(self.desc.join(" "));
+            Ok(())
+        })?;
 
         println!(
             "New project description set {}",
             fg_color!("successfully", Green)
         );
+
+        Ok(())
     }
 }
 
 impl super::Command for SetRepoArgs {
-    fn run(self, file_name: &str) {
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
-        };
-
+    fn run(self, file_name: &str) -> Result<(), Error> {
         if Url::parse(&self.repo).is_err() {
-            let _ = Cli::command()
-                .error(ErrorKind::InvalidValue, "An invalid URL was specified")
-                .print();
-            return;
+            return Err(Error::Usage("An invalid URL was specified".to_string()));
         }
 
-        data.information.repo = Some(self.repo);
-
-        utils::write_data(file_name, &data);
+        utils::update_data(file_name, "set project repository link", |data| {
+            data.information.repo = Some(self.repo);
+            Ok(())
+        })?;
 
         println!(
             "New project repository link set {}",
             fg_color!("successfully", Green)
         );
+
+        Ok(())
     }
 }
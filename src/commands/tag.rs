@@ -0,0 +1,151 @@
+use crate::data::ItemKind;
+use crate::{fg_color, utils, Error};
+use clap::builder::styling;
+use clap::{Args, Parser, Subcommand};
+
+#[derive(Subcommand)]
+pub enum Commands {
+    Tag(Tag),
+}
+
+#[derive(Parser)]
+pub struct Tag {
+    #[structopt(subcommand)]
+    pub tag_commands: TagCommands,
+}
+
+#[derive(Subcommand)]
+pub enum TagCommands {
+    /// Add tag(s) to a task or a note
+    Add(TagAddArgs),
+    /// Remove tag(s) from a task or a note
+    Remove(TagRemoveArgs),
+}
+
+#[derive(Args)]
+pub struct TagAddArgs {
+    /// The kind of the item that you will add tags to
+    #[arg(value_enum)]
+    pub kind: ItemKind,
+    /// The group that the item belongs to
+    pub group_name: String,
+    /// The ID of the item
+    pub id: usize,
+    /// The tag(s) that you want to add
+    #[arg(value_parser = utils::parse_tag)]
+    pub tags: Vec<String>,
+}
+
+#[derive(Args)]
+pub struct TagRemoveArgs {
+    /// The kind of the item that you will remove tags from
+    #[arg(value_enum)]
+    pub kind: ItemKind,
+    /// The group that the item belongs to
+    pub group_name: String,
+    /// The ID of the item
+    pub id: usize,
+    /// The tag(s) that you want to remove
+    #[arg(value_parser = utils::parse_tag)]
+    pub tags: Vec<String>,
+}
+
+/// Applies `f` to the tags of an item in a group
+fn update_tags(
+    file_name: &str,
+    kind: ItemKind,
+    group_name: &str,
+    id: usize,
+    tags: &[String],
+    summary: &str,
+    f: impl FnOnce(&mut Vec<String>),
+) -> Result<(), Error> {
+    if tags.is_empty() {
+        return Err(Error::Usage("No tags were specified".to_string()));
+    }
+
+    utils::update_data(file_name, summary, |data| {
+        let Some(group) = data.groups.get_mut(group_name) else {
+            return Err(Error::Missing(format!(
+                "Specified group `{}` does not exist",
+                group_name
+            )));
+        };
+
+        let Some(item_tags) = kind.tags_mut(group, id) else {
+            return Err(Error::Missing(format!(
+                "No {} with ID {} exists in the group",
+                kind, id
+            )));
+        };
+
+        f(item_tags);
+        Ok(())
+    })
+}
+
+impl super::Command for TagAddArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        update_tags(
+            file_name,
+            self.kind,
+            &self.group_name,
+            self.id,
+            &self.tags,
+            &format!(
+                "tag {} {} in group {} with {}",
+                self.kind,
+                self.id,
+                self.group_name,
+                self.tags.join(", ")
+            ),
+            |tags| {
+                utils::merge_tags(tags, &self.tags);
+            },
+        )?;
+
+        println!(
+            "{} added tags to {} {} in group `{}`: {}",
+            fg_color!("Successfully", Green),
+            self.kind,
+            self.id,
+            fg_color!(self.group_name, Yellow),
+            self.tags.join(", ")
+        );
+
+        Ok(())
+    }
+}
+
+impl super::Command for TagRemoveArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        update_tags(
+            file_name,
+            self.kind,
+            &self.group_name,
+            self.id,
+            &self.tags,
+            &format!(
+                "remove tag(s) {} from {} {} in group {}",
+                self.tags.join(", "),
+                self.kind,
+                self.id,
+                self.group_name
+            ),
+            |tags| {
+                tags.retain(|t| !self.tags.contains(t));
+            },
+        )?;
+
+        println!(
+            "{} removed tags from {} {} in group `{}`: {}",
+            fg_color!("Successfully", Green),
+            self.kind,
+            self.id,
+            fg_color!(self.group_name, Yellow),
+            self.tags.join(", ")
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use crate::{fg_color, output, utils, Error, Format};
+use clap::builder::styling;
+use clap::Args;
+use serde::Serialize;
+use std::collections::HashMap;
+
+#[derive(Args)]
+pub struct TagsArgs;
+
+/// How many items use a tag, as it appears in structured output
+#[derive(Serialize)]
+struct TagCount<'a> {
+    tag: &'a str,
+    tasks: usize,
+    notes: usize,
+}
+
+#[derive(Serialize)]
+struct TagsView<'a> {
+    tags: Vec<TagCount<'a>>,
+}
+
+impl super::Command for TagsArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        super::Report::report(self, file_name, Format::Text)
+    }
+}
+
+impl super::Report for TagsArgs {
+    fn report(self, file_name: &str, format: Format) -> Result<(), Error> {
+        let data = utils::get_data(file_name)?;
+
+        // tag -> (task count, note count)
+        let mut counts: HashMap<&str, (usize, usize)> = HashMap::new();
+        for group in data.groups.values() {
+            for task in group.all_tasks() {
+                for tag in &task.tags {
+                    counts.entry(tag).or_default().0 += 1;
+                }
+            }
+            for note in &group.notes {
+                for tag in &note.tags {
+                    counts.entry(tag).or_default().1 += 1;
+                }
+            }
+        }
+
+        let mut counts: Vec<(&str, (usize, usize))> = counts.into_iter().collect();
+        counts.sort_by(|(a, (a_tasks, a_notes)), (b, (b_tasks, b_notes))| {
+            (b_tasks + b_notes).cmp(&(a_tasks + a_notes)).then(a.cmp(b))
+        });
+
+        if !format.is_text() {
+            let view = TagsView {
+                tags: counts
+                    .into_iter()
+                    .map(|(tag, (tasks, notes))| TagCount { tag, tasks, notes })
+                    .collect(),
+            };
+            return output::print(format, &view, &view.tags);
+        }
+
+        if counts.is_empty() {
+            println!("No tags are used in the project");
+            return Ok(());
+        }
+
+        println!();
+        for (tag, (tasks, notes)) in counts {
+            println!(
+                "  {} - {} task(s), {} note(s)",
+                fg_color!(format!("#{}", tag), Cyan),
+                tasks,
+                notes
+            );
+        }
+        println!();
+
+        Ok(())
+    }
+}
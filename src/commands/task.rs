@@ -1,8 +1,10 @@
-use crate::data::{self, Group, TaskState};
-use crate::{fg_color, utils, Cli};
+use crate::data::{self, Group, Link, Priority, Project, TaskState};
+use crate::output::{self, TaskView};
+use crate::{fg_color, query, utils, Error, Format, Query};
 use clap::builder::styling;
-use clap::error::ErrorKind;
-use clap::{Args, CommandFactory, Parser, Subcommand};
+use clap::{ArgGroup, Args, Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+use std::cmp::Reverse;
 
 #[derive(Subcommand)]
 pub enum Commands {
@@ -21,8 +23,28 @@ pub enum TaskCommands {
     Complete(TaskCompleteArgs),
     /// Set task(s) as incomplete
     Undo(TaskUndoArgs),
+    /// Set task(s) as in progress
+    Start(TaskStartArgs),
+    /// Set task(s) as blocked
+    Block(TaskBlockArgs),
+    /// Set task(s) as cancelled
+    Cancel(TaskCancelArgs),
+    /// Set the priority of task(s)
+    Priority(TaskPriorityArgs),
+    /// Set or clear the due date of task(s)
+    Due(TaskDueArgs),
     /// View the progress of a group or watched groups
     Progress(TaskProgressArgs),
+    /// Move a task to another position in its group
+    Reorder(TaskReorderArgs),
+    /// Sort the tasks of a group, changing their stored order
+    Sort(TaskSortArgs),
+    /// Make a task wait on other tasks, in any group
+    Link(TaskLinkArgs),
+    /// Stop a task from waiting on other tasks
+    Unlink(TaskUnlinkArgs),
+    /// View the tasks that a task waits on, and the tasks that wait on it
+    Deps(TaskDepsArgs),
 }
 
 #[derive(Args)]
@@ -31,6 +53,12 @@ pub struct TaskCompleteArgs {
     pub group_name: String,
     /// The ID(s) of the task(s) that you want to set as complete
     pub ids: Vec<usize>,
+    /// Use this flag to also complete the sub-tasks of the task(s), cancelled ones are left as is
+    #[arg(short, long)]
+    pub cascade: bool,
+    /// Use this flag to complete the task(s) even if tasks they wait on aren't finished
+    #[arg(short, long)]
+    pub force: bool,
 }
 
 #[derive(Args)]
@@ -41,6 +69,54 @@ pub struct TaskUndoArgs {
     pub ids: Vec<usize>,
 }
 
+#[derive(Args)]
+pub struct TaskStartArgs {
+    /// The group that the task(s) belong to
+    pub group_name: String,
+    /// The ID(s) of the task(s) that you want to set as in progress
+    pub ids: Vec<usize>,
+}
+
+#[derive(Args)]
+pub struct TaskBlockArgs {
+    /// The group that the task(s) belong to
+    pub group_name: String,
+    /// The ID(s) of the task(s) that you want to set as blocked
+    pub ids: Vec<usize>,
+    /// What the task(s) are waiting on
+    #[arg(short, long)]
+    pub reason: Option<String>,
+}
+
+#[derive(Args)]
+pub struct TaskCancelArgs {
+    /// The group that the task(s) belong to
+    pub group_name: String,
+    /// The ID(s) of the task(s) that you want to set as cancelled
+    pub ids: Vec<usize>,
+}
+
+#[derive(Args)]
+pub struct TaskPriorityArgs {
+    /// The group that the task(s) belong to
+    pub group_name: String,
+    /// The new priority of the task(s)
+    #[arg(value_enum)]
+    pub priority: Priority,
+    /// The ID(s) of the task(s) that you want to set the priority of
+    pub ids: Vec<usize>,
+}
+
+#[derive(Args)]
+pub struct TaskDueArgs {
+    /// The group that the task(s) belong to
+    pub group_name: String,
+    /// The new due date (YYYY-MM-DD, today, tomorrow, +3d, +2w or a weekday), or `none` to clear it
+    pub date: String,
+    /// The ID(s) of the task(s) that you want to set the due date of
+    pub ids: Vec<usize>,
+}
+
 #[derive(Args)]
 pub struct TaskProgressArgs {
     /// The group that you want to see the progress for
@@ -48,40 +124,273 @@ pub struct TaskProgressArgs {
     /// Use this flag to view progress of all groups in the project (ignores GROUP_NAME)
     #[arg(short, long)]
     pub all: bool,
+    /// Use this flag to sort tasks by priority, highest first
+    #[arg(short, long)]
+    pub priority: bool,
+    #[command(flatten)]
+    pub query: Query,
+}
+
+#[derive(Args, Default)]
+#[command(group(ArgGroup::new("position").required(true)))]
+pub struct TaskReorderArgs {
+    /// The group that the task belongs to
+    pub group_name: String,
+    /// The ID of the task that you want to move
+    pub id: usize,
+    /// The position to move the task to, starting from 1
+    #[arg(short, long, group = "position")]
+    pub to: Option<usize>,
+    /// Use this flag to move the task up by one
+    #[arg(short, long, group = "position")]
+    pub up: bool,
+    /// Use this flag to move the task down by one
+    #[arg(short, long, group = "position")]
+    pub down: bool,
+    /// Use this flag to move the task to the top of the group
+    #[arg(long, group = "position")]
+    pub top: bool,
+    /// Use this flag to move the task to the bottom of the group
+    #[arg(long, group = "position")]
+    pub bottom: bool,
+}
+
+#[derive(Args)]
+pub struct TaskSortArgs {
+    /// The group that you want to sort the tasks of
+    pub group_name: String,
+    /// What to sort the tasks by
+    #[arg(short, long, value_enum)]
+    pub by: SortKey,
+}
+
+#[derive(Args)]
+pub struct TaskLinkArgs {
+    /// The group that the task belongs to
+    pub group_name: String,
+    /// The ID of the task that has to wait
+    pub id: usize,
+    /// The ID of a task that has to be finished first, can be used multiple times
+    #[arg(short, long, required = true)]
+    pub blocked_by: Vec<usize>,
+}
+
+#[derive(Args)]
+pub struct TaskUnlinkArgs {
+    /// The group that the task belongs to
+    pub group_name: String,
+    /// The ID of the task that waits
+    pub id: usize,
+    /// The ID of a task that it shouldn't wait on anymore, can be used multiple times
+    #[arg(short, long, required = true)]
+    pub blocked_by: Vec<usize>,
+}
+
+#[derive(Args)]
+pub struct TaskDepsArgs {
+    /// The group that the task belongs to
+    pub group_name: String,
+    /// The ID of the task that you want to see the dependencies of
+    pub id: usize,
+}
+
+/// Orders that the tasks of a group can be sorted in
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SortKey {
+    /// Tasks in progress first, then incomplete, blocked, complete and cancelled ones
+    State,
+    /// Alphabetically, ignoring case
+    Text,
+    /// Highest priority first
+    Priority,
+    /// Earliest due date first, tasks without one go last
+    Due,
+}
+
+impl TaskReorderArgs {
+    /// The index that the task should end up at, given its current index and the group's size
+    fn target(&self, index: usize, len: usize) -> Result<usize, Error> {
+        if let Some(to) = self.to {
+            if to == 0 || to > len {
+                return Err(Error::Usage(format!(
+                    "Position {} is out of range, the group has positions 1 to {}",
+                    to, len
+                )));
+            }
+            return Ok(to - 1);
+        }
+
+        Ok(if self.up {
+            index.saturating_sub(1)
+        } else if self.down {
+            (index + 1).min(len - 1)
+        } else if self.top {
+            0
+        } else {
+            len - 1
+        })
+    }
+}
+
+/// Where a state goes when sorting by state, things being worked on come first
+fn state_rank(state: &TaskState) -> usize {
+    match state {
+        TaskState::InProgress => 0,
+        TaskState::Incomplete => 1,
+        TaskState::Blocked { .. } => 2,
+        TaskState::Complete => 3,
+        TaskState::Cancelled => 4,
+    }
+}
+
+impl TaskProgressArgs {
+    /// Keeps only the tasks of a group that match the query, or `None` if a query leaves nothing
+    fn matching(&self, mut group: Group) -> Option<Group> {
+        if self.priority {
+            group.tasks.sort_by_key(|t| Reverse(t.priority));
+        }
+
+        if self.query.is_active() {
+            group.tasks = group
+                .tasks
+                .iter()
+                .filter_map(|t| self.query.prune(t))
+                .collect();
+            // queries can span many groups, so only the ones with matches are shown
+            if group.tasks.is_empty() {
+                return None;
+            }
+        }
+
+        Some(group)
+    }
+}
+
+/// Progress of a group's tasks as it appears in structured output
+#[derive(Serialize)]
+struct Progress {
+    group: String,
+    complete: usize,
+    /// Tasks that count towards progress, which leaves out cancelled ones
+    total: usize,
+    cancelled: usize,
+    percentage: usize,
+    weighted_percentage: usize,
+}
+
+#[derive(Serialize)]
+struct ProgressView {
+    #[serde(flatten)]
+    progress: Progress,
+    tasks: Vec<TaskView>,
+}
+
+#[derive(Serialize)]
+struct ProgressReport {
+    groups: Vec<ProgressView>,
+}
+
+impl Progress {
+    /// Counts the top level tasks, unfinished tasks with sub-tasks count as partly done
+    fn new(group: &Group) -> Self {
+        // cancelled tasks are out of scope, so they don't count towards progress
+        let counted: Vec<&data::Task> = group
+            .tasks
+            .iter()
+            .filter(|t| t.state != TaskState::Cancelled)
+            .collect();
+        let complete = counted
+            .iter()
+            .filter(|t| t.state == TaskState::Complete)
+            .count();
+
+        let done: f64 = counted.iter().map(|t| t.progress()).sum();
+        let weighted_done: f64 = counted
+            .iter()
+            .map(|t| t.progress() * t.priority.weight() as f64)
+            .sum();
+        let weight: usize = counted.iter().map(|t| t.priority.weight()).sum();
+
+        Progress {
+            group: group.name.clone(),
+            complete,
+            total: counted.len(),
+            cancelled: group.tasks.len() - counted.len(),
+            percentage: percentage(done, counted.len()),
+            weighted_percentage: percentage(weighted_done, weight),
+        }
+    }
+}
+
+/// `done` out of `total` as a whole percentage, rounded down
+fn percentage(done: f64, total: usize) -> usize {
+    if total == 0 {
+        return 0;
+    }
+    // partial progress is an average, so a bit is added to not round 0.999.. down
+    (done * 100.0 / total as f64 + 1e-9) as usize
+}
+
+/// Prints a task and, indented under it, its sub-tasks with how far along each one is
+fn display_task(task: &data::Task, depth: usize) {
+    let counts = if task.subtasks.is_empty() {
+        String::new()
+    } else {
+        let (complete, total) = task.subtask_counts();
+        let partial = if task.state.is_closed() {
+            String::new()
+        } else {
+            format!(", %{}", percentage(task.progress(), 1))
+        };
+        format!(
+            " {}",
+            fg_color!(format!("({}/{}{})", complete, total, partial), BrightBlack)
+        )
+    };
+
+    println!(
+        "  {}[{}] {}{}",
+        "    ".repeat(depth),
+        task.state.symbol(),
+        utils::format_task(task),
+        counts
+    );
+    for subtask in &task.subtasks {
+        display_task(subtask, depth + 1);
+    }
 }
 
 fn display_progress(group: Group) {
     let max_chars = 40;
     let mut used_chars = 0;
+    let progress = Progress::new(&group);
 
-    let finished_tasks: Vec<data::Task> = group
+    let finished_tasks: Vec<&data::Task> = group
         .tasks
-        .clone()
-        .into_iter()
+        .iter()
         .filter(|t| t.state == TaskState::Complete)
         .collect();
 
-    let unfinished_tasks: Vec<data::Task> = group
+    let unfinished_tasks: Vec<&data::Task> = group
         .tasks
-        .clone()
-        .into_iter()
-        .filter(|t| t.state == TaskState::Incomplete)
+        .iter()
+        .filter(|t| !t.state.is_closed())
         .collect();
 
-    let progress_percentage = if !group.tasks.is_empty() {
-        finished_tasks.len() * 100 / group.tasks.len()
-    } else {
-        0
-    };
+    let cancelled_tasks: Vec<&data::Task> = group
+        .tasks
+        .iter()
+        .filter(|t| t.state == TaskState::Cancelled)
+        .collect();
 
     let mut parsed_progress = String::new();
 
     while used_chars != max_chars {
-        if used_chars * 100 / max_chars >= progress_percentage {
+        if used_chars * 100 / max_chars >= progress.percentage {
             parsed_progress.push(' ');
         } else {
             parsed_progress.push('=');
-            if (used_chars + 1) * 100 / max_chars >= progress_percentage
+            if (used_chars + 1) * 100 / max_chars >= progress.percentage
                 && used_chars + 1 != max_chars
             {
                 parsed_progress.push('>');
@@ -90,76 +399,240 @@ fn display_progress(group: Group) {
         used_chars += 1;
     }
 
+    let cancelled = if progress.cancelled == 0 {
+        String::new()
+    } else {
+        format!(", {} cancelled", progress.cancelled)
+    };
+
     println!("\n[{}]", fg_color!(group.name, Yellow));
     println!(
-        "[{}] %{}\n",
+        "[{}] %{} ({}/{} tasks{}, %{} weighted by priority)\n",
         fg_color!(parsed_progress, BrightBlack),
-        progress_percentage
+        progress.percentage,
+        progress.complete,
+        progress.total,
+        cancelled,
+        progress.weighted_percentage
     );
 
-    for task in unfinished_tasks {
-        println!("  [ ] {}", task.task);
+    for task in unfinished_tasks
+        .iter()
+        .chain(&finished_tasks)
+        .chain(&cancelled_tasks)
+    {
+        display_task(task, 0);
     }
-    for task in finished_tasks {
-        println!("  [x] {}", task.task);
+}
+
+/// Applies `f` to the tasks with the specified IDs in a group, returns the formatted IDs
+///
+/// The change is summarized in the history as `<change> task(s) <ids> in group <group_name>`.
+fn update_tasks(
+    file_name: &str,
+    change: &str,
+    group_name: &str,
+    ids: &[usize],
+    f: impl Fn(&mut data::Task),
+) -> Result<String, Error> {
+    update_tasks_then(file_name, change, group_name, ids, f, |_| Ok(()))
+        .map(|(formatted_ids, _)| formatted_ids)
+}
+
+/// Sets the state of the tasks with `f`, like `update_tasks`
+///
+/// Parents of the changed tasks are completed or reopened along with them to match their
+/// sub-tasks, except that parents waiting on unfinished tasks aren't completed. Returns the
+/// formatted IDs and the parents that changed.
+fn update_states(
+    file_name: &str,
+    change: &str,
+    group_name: &str,
+    ids: &[usize],
+    f: impl Fn(&mut data::Task),
+) -> Result<(String, ParentChanges), Error> {
+    update_tasks_then(file_name, change, group_name, ids, f, |data| {
+        Ok(sync_parents(data, group_name, ids))
+    })
+}
+
+/// Completes or reopens the parents of the changed tasks to match their sub-tasks
+fn sync_parents(data: &mut Project, group_name: &str, ids: &[usize]) -> ParentChanges {
+    // a parent that waits on unfinished tasks is left for the user to complete
+    let waiting: Vec<usize> = data.groups[group_name]
+        .all_tasks()
+        .into_iter()
+        .filter(|t| !data.unfinished_blockers(t).is_empty())
+        .map(|t| t.id)
+        .collect();
+    let group = data
+        .groups
+        .get_mut(group_name)
+        .expect("The group was checked");
+    let (completed, reopened) = group.update_parents(ids, |t| !waiting.contains(&t.id));
+
+    ParentChanges {
+        completed,
+        reopened,
     }
 }
 
-impl super::Command for TaskCompleteArgs {
-    fn run(self, file_name: &str) {
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
-        };
+/// Applies `f` to the tasks, then runs `then` on the changed project in the same update
+fn update_tasks_then<T>(
+    file_name: &str,
+    change: &str,
+    group_name: &str,
+    ids: &[usize],
+    f: impl Fn(&mut data::Task),
+    then: impl FnOnce(&mut Project) -> Result<T, Error>,
+) -> Result<(String, T), Error> {
+    if ids.is_empty() {
+        return Err(Error::Usage("No task ID was specified".to_string()));
+    }
 
-        if !data.groups.contains_key(&self.group_name) {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::InvalidValue,
-                    format!("Specified group `{}` does not exist", self.group_name),
-                )
-                .print();
-            return;
-        } else if self.ids.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::MissingRequiredArgument,
-                    "No task ID was specified",
-                )
-                .print();
-            return;
+    let summary = format!(
+        "{} task(s) {} in group {}",
+        change,
+        utils::format_ids(ids),
+        group_name
+    );
+    let value = utils::update_data(file_name, &summary, |data| {
+        if !data.groups.contains_key(group_name) {
+            return Err(Error::Missing(format!(
+                "Specified group `{}` does not exist",
+                group_name
+            )));
         }
 
-        let mut group = data.get_group(&self.group_name).clone();
+        let mut group = data.get_group(group_name)?;
         let mut invalid_ids: Vec<String> = vec![];
 
-        for id in &self.ids {
-            if group.tasks.len() < *id {
+        for id in ids {
+            if group.find_task(*id).is_none() {
                 invalid_ids.push(id.to_string());
             }
         }
 
         if !invalid_ids.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::InvalidValue,
-                    format!(
-                        "Some given IDs are out of range: {}",
-                        invalid_ids.join(", ")
-                    ),
-                )
-                .print();
-            return;
+            return Err(Error::Missing(format!(
+                "Some given IDs do not exist in the group: {}",
+                invalid_ids.join(", ")
+            )));
         }
 
+        for id in ids {
+            if let Some(task) = group.find_task_mut(*id) {
+                f(task);
+            }
+        }
+        data.groups.insert(group_name.to_string(), group);
+
+        then(data)
+    })?;
+
+    Ok((utils::format_ids(ids), value))
+}
+
+/// Parents that `update_states` changed to match their sub-tasks
+struct ParentChanges {
+    completed: Vec<usize>,
+    reopened: Vec<usize>,
+}
+
+impl ParentChanges {
+    fn print(&self) {
+        if !self.completed.is_empty() {
+            println!(
+                "Also completed task(s) {}, as all of their sub-tasks are done",
+                utils::format_ids(&self.completed)
+            );
+        }
+        if !self.reopened.is_empty() {
+            println!(
+                "Also reopened task(s) {}, as one of their sub-tasks isn't done anymore",
+                utils::format_ids(&self.reopened)
+            );
+        }
+    }
+}
+
+impl TaskCompleteArgs {
+    /// Checks that the tasks don't wait on unfinished tasks, unless completing them is forced,
+    /// returns what the tasks wait on when they're completed anyway
+    ///
+    /// This runs in the same update as the change, so tasks that are completed together don't
+    /// hold each other back and nothing can be reopened between the check and the change.
+    fn check_blockers(&self, data: &Project) -> Result<Vec<String>, Error> {
+        let Some(group) = data.groups.get(&self.group_name) else {
+            return Ok(vec![]);
+        };
+
+        let mut completing: Vec<&data::Task> = vec![];
         for id in &self.ids {
-            group.tasks[id - 1].state = TaskState::Complete;
+            if let Some(task) = group.find_task(*id) {
+                completing.push(task);
+                if self.cascade {
+                    completing.extend(task.all_subtasks());
+                }
+            }
+        }
+        let ids: Vec<usize> = completing.iter().map(|t| t.id).collect();
+
+        let mut waiting: Vec<String> = vec![];
+        for task in completing {
+            let blockers: Vec<usize> = data
+                .unfinished_blockers(task)
+                .into_iter()
+                .filter(|id| !ids.contains(id))
+                .collect();
+            if !blockers.is_empty() {
+                waiting.push(format!(
+                    "task {} waits on {}",
+                    task.id,
+                    utils::format_ids(&blockers)
+                ));
+            }
         }
 
-        data.groups.insert(self.group_name.clone(), group);
-        utils::write_data(file_name, &data);
+        if !waiting.is_empty() && !self.force {
+            return Err(Error::Invalid(format!(
+                "Unfinished tasks are in the way, {} (Use --force flag to complete anyway)",
+                waiting.join("; ")
+            )));
+        }
+
+        Ok(waiting)
+    }
+}
+
+impl super::Command for TaskCompleteArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let complete = |task: &mut data::Task| {
+            if self.cascade {
+                task.complete_all();
+            } else {
+                task.set_state(TaskState::Complete);
+            }
+        };
+        let (formatted_ids, (waiting, parents)) = update_tasks_then(
+            file_name,
+            "complete",
+            &self.group_name,
+            &self.ids,
+            complete,
+            |data| {
+                let waiting = self.check_blockers(data)?;
+                Ok((waiting, sync_parents(data, &self.group_name, &self.ids)))
+            },
+        )?;
 
-        let mut formatted_ids: String = self.ids.iter().map(|i| i.to_string() + ", ").collect();
-        formatted_ids.truncate(formatted_ids.len() - 2);
+        if !waiting.is_empty() {
+            println!(
+                "{} completed anyway, {}",
+                fg_color!("Warning:", Red),
+                waiting.join("; ")
+            );
+        }
 
         println!(
             "{} set following tasks for group `{}` as complete: {}",
@@ -167,106 +640,492 @@ impl super::Command for TaskCompleteArgs {
             fg_color!(self.group_name, Yellow),
             formatted_ids
         );
+        parents.print();
+
+        Ok(())
     }
 }
 
 impl super::Command for TaskUndoArgs {
-    fn run(self, file_name: &str) {
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
-        };
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let (formatted_ids, parents) =
+            update_states(file_name, "reopen", &self.group_name, &self.ids, |task| {
+                task.set_state(TaskState::Incomplete);
+            })?;
 
-        if !data.groups.contains_key(&self.group_name) {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::InvalidValue,
-                    format!("Specified group `{}` does not exist", self.group_name),
-                )
-                .print();
-            return;
-        } else if self.ids.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::MissingRequiredArgument,
-                    "No task ID was specified",
-                )
-                .print();
-            return;
-        }
+        println!(
+            "{} set following tasks for group `{}` as incomplete: {}",
+            fg_color!("Successfully", Green),
+            fg_color!(self.group_name, Yellow),
+            formatted_ids
+        );
+        parents.print();
 
-        let mut group = data.get_group(&self.group_name).clone();
-        let mut invalid_ids: Vec<String> = vec![];
+        Ok(())
+    }
+}
 
-        for id in &self.ids {
-            if group.tasks.len() < *id {
-                invalid_ids.push(id.to_string());
-            }
-        }
+impl super::Command for TaskStartArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let (formatted_ids, parents) =
+            update_states(file_name, "start", &self.group_name, &self.ids, |task| {
+                task.set_state(TaskState::InProgress);
+            })?;
 
-        if !invalid_ids.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::InvalidValue,
-                    format!(
-                        "Some given IDs are out of range: {}",
-                        invalid_ids.join(", ")
-                    ),
-                )
-                .print();
-            return;
-        }
+        println!(
+            "{} set following tasks for group `{}` as in progress: {}",
+            fg_color!("Successfully", Green),
+            fg_color!(self.group_name, Yellow),
+            formatted_ids
+        );
+        parents.print();
 
-        for id in &self.ids {
-            group.tasks[id - 1].state = TaskState::Incomplete;
-        }
+        Ok(())
+    }
+}
+
+impl super::Command for TaskBlockArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let (formatted_ids, parents) =
+            update_states(file_name, "block", &self.group_name, &self.ids, |task| {
+                task.set_state(TaskState::Blocked {
+                    reason: self.reason.clone(),
+                });
+            })?;
+
+        println!(
+            "{} set following tasks for group `{}` as blocked: {}",
+            fg_color!("Successfully", Green),
+            fg_color!(self.group_name, Yellow),
+            formatted_ids
+        );
+        parents.print();
 
-        data.groups.insert(self.group_name.clone(), group);
-        utils::write_data(file_name, &data);
+        Ok(())
+    }
+}
 
-        let mut formatted_ids: String = self.ids.iter().map(|i| i.to_string() + ", ").collect();
-        formatted_ids.truncate(formatted_ids.len() - 2);
+impl super::Command for TaskCancelArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let (formatted_ids, parents) =
+            update_states(file_name, "cancel", &self.group_name, &self.ids, |task| {
+                task.set_state(TaskState::Cancelled);
+            })?;
 
         println!(
-            "{} set following tasks for group `{}` as incomplete: {}",
+            "{} set following tasks for group `{}` as cancelled: {}",
             fg_color!("Successfully", Green),
             fg_color!(self.group_name, Yellow),
             formatted_ids
         );
+        parents.print();
+
+        Ok(())
     }
 }
 
-impl super::Command for TaskProgressArgs {
-    fn run(self, file_name: &str) {
-        let Some(data) = utils::get_data(file_name) else {
-            return;
+impl super::Command for TaskPriorityArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let change = format!("set {} priority on", self.priority);
+        let formatted_ids =
+            update_tasks(file_name, &change, &self.group_name, &self.ids, |task| {
+                task.priority = self.priority;
+            })?;
+
+        println!(
+            "{} set following tasks for group `{}` to {} priority: {}",
+            fg_color!("Successfully", Green),
+            fg_color!(self.group_name, Yellow),
+            self.priority,
+            formatted_ids
+        );
+
+        Ok(())
+    }
+}
+
+impl super::Command for TaskDueArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let due = if self.date.eq_ignore_ascii_case("none") {
+            None
+        } else {
+            Some(utils::parse_date(&self.date, utils::today()).map_err(Error::Usage)?)
         };
 
-        if self.all {
-            for group in data.groups.keys() {
-                display_progress(data.get_group(group));
-            }
-            return;
+        let due_text = due.map_or("no due date".to_string(), |due| format!("due {}", due));
+        let change = format!("set {} on", due_text);
+        let formatted_ids =
+            update_tasks(file_name, &change, &self.group_name, &self.ids, |task| {
+                task.due = due;
+            })?;
+
+        println!(
+            "{} set following tasks for group `{}` as {}: {}",
+            fg_color!("Successfully", Green),
+            fg_color!(self.group_name, Yellow),
+            due_text,
+            formatted_ids
+        );
+
+        Ok(())
+    }
+}
+
+impl super::Command for TaskProgressArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        super::Report::report(self, file_name, Format::Text)
+    }
+}
+
+impl super::Report for TaskProgressArgs {
+    fn report(self, file_name: &str, format: Format) -> Result<(), Error> {
+        let data = utils::get_data(file_name)?;
+        let groups: Vec<Group> =
+            query::select_groups(&data, self.group_name.as_deref(), self.all, &self.query)?
+                .iter()
+                .map(|g| data.get_group(g))
+                .collect::<Result<Vec<Group>, Error>>()?
+                .into_iter()
+                .filter_map(|g| self.matching(g))
+                .collect();
+
+        if !format.is_text() {
+            let today = utils::today();
+            let report = ProgressReport {
+                groups: groups
+                    .iter()
+                    .map(|g| ProgressView {
+                        progress: Progress::new(g),
+                        tasks: g
+                            .tasks
+                            .iter()
+                            .map(|t| TaskView::new(&g.name, t, today))
+                            .collect(),
+                    })
+                    .collect(),
+            };
+            let rows: Vec<&Progress> = report.groups.iter().map(|g| &g.progress).collect();
+            return output::print(format, &report, &rows);
         }
 
-        if self.group_name.is_some() {
-            let group_name = self.group_name.unwrap();
-            let group = data.get_group(&group_name).clone();
-            let groups = data.get_group_descendants(&group_name);
+        if groups.is_empty() {
+            println!("No tasks match the given filters");
+        }
+        for group in groups {
             display_progress(group);
-            for g in &groups {
-                display_progress(data.get_group(g));
+        }
+
+        Ok(())
+    }
+}
+
+impl super::Command for TaskReorderArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = format!("reorder task {} in group {}", self.id, self.group_name);
+        let position = utils::update_data(file_name, &summary, |data| {
+            let Some(group) = data.groups.get_mut(&self.group_name) else {
+                return Err(Error::Missing(format!(
+                    "Specified group `{}` does not exist",
+                    self.group_name
+                )));
+            };
+            let Some(index) = group.task_index(self.id) else {
+                return Err(Error::Missing(format!(
+                    "No task with ID {} exists in the group",
+                    self.id
+                )));
+            };
+
+            let target = self.target(index, group.tasks.len())?;
+            let task = group.tasks.remove(index);
+            group.tasks.insert(target, task);
+            Ok(target + 1)
+        })?;
+
+        println!(
+            "{} moved task {} in group `{}` to position {}",
+            fg_color!("Successfully", Green),
+            self.id,
+            fg_color!(self.group_name, Yellow),
+            position
+        );
+
+        Ok(())
+    }
+}
+
+impl super::Command for TaskSortArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let key = self
+            .by
+            .to_possible_value()
+            .expect("Sort keys aren't skipped");
+        let summary = format!(
+            "sort tasks in group {} by {}",
+            self.group_name,
+            key.get_name()
+        );
+        utils::update_data(file_name, &summary, |data| {
+            let Some(group) = data.groups.get_mut(&self.group_name) else {
+                return Err(Error::Missing(format!(
+                    "Specified group `{}` does not exist",
+                    self.group_name
+                )));
+            };
+
+            // sorts are stable, so tasks that are equal keep their order
+            match self.by {
+                SortKey::State => group.tasks.sort_by_key(|t| state_rank(&t.state)),
+                SortKey::Text => group.tasks.sort_by_key(|t| t.task.to_lowercase()),
+                SortKey::Priority => group.tasks.sort_by_key(|t| Reverse(t.priority)),
+                SortKey::Due => group.tasks.sort_by_key(|t| (t.due.is_none(), t.due)),
             }
-        } else if !data.active_groups.is_empty() {
-            for g in &data.active_groups {
-                display_progress(data.get_group(g));
+            Ok(())
+        })?;
+
+        println!(
+            "{} sorted the tasks of group `{}` by {}",
+            fg_color!("Successfully", Green),
+            fg_color!(self.group_name, Yellow),
+            key.get_name()
+        );
+
+        Ok(())
+    }
+}
+
+/// Checks that a group exists and has a task or sub-task with the ID
+fn check_task(data: &Project, group_name: &str, id: usize) -> Result<(), Error> {
+    let Some(group) = data.groups.get(group_name) else {
+        return Err(Error::Missing(format!(
+            "Specified group `{}` does not exist",
+            group_name
+        )));
+    };
+    if group.find_task(id).is_none() {
+        return Err(Error::Missing(format!(
+            "No task with ID {} exists in the group",
+            id
+        )));
+    }
+
+    Ok(())
+}
+
+impl super::Command for TaskLinkArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = format!(
+            "link task {} in group {} to blocker(s) {}",
+            self.id,
+            self.group_name,
+            utils::format_ids(&self.blocked_by)
+        );
+        utils::update_data(file_name, &summary, |data| {
+            check_task(data, &self.group_name, self.id)?;
+
+            for blocker in &self.blocked_by {
+                if data.find_task(*blocker).is_none() {
+                    return Err(Error::Missing(format!(
+                        "No task with ID {} exists in the project",
+                        blocker
+                    )));
+                }
+                if *blocker == self.id {
+                    return Err(Error::Invalid("A task can't wait on itself".to_string()));
+                }
+                if data.would_cycle(self.id, *blocker) {
+                    return Err(Error::Invalid(format!(
+                        "Task {} already waits on task {}, so task {} can't wait on it",
+                        blocker, self.id, self.id
+                    )));
+                }
             }
-        } else {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::Io,
-                    "No groups being watched to display the progress for",
-                )
-                .print();
+
+            let task = data
+                .groups
+                .get_mut(&self.group_name)
+                .and_then(|g| g.find_task_mut(self.id))
+                .expect("The task was checked");
+            for blocker in &self.blocked_by {
+                if !task.blocked_by.contains(blocker) {
+                    task.blocked_by.push(*blocker);
+                }
+            }
+            Ok(())
+        })?;
+
+        println!(
+            "{} set task {} in group `{}` to wait on: {}",
+            fg_color!("Successfully", Green),
+            self.id,
+            fg_color!(self.group_name, Yellow),
+            utils::format_ids(&self.blocked_by)
+        );
+
+        Ok(())
+    }
+}
+
+impl super::Command for TaskUnlinkArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = format!(
+            "unlink task {} in group {} from blocker(s) {}",
+            self.id,
+            self.group_name,
+            utils::format_ids(&self.blocked_by)
+        );
+        utils::update_data(file_name, &summary, |data| {
+            check_task(data, &self.group_name, self.id)?;
+
+            let task = data
+                .groups
+                .get_mut(&self.group_name)
+                .and_then(|g| g.find_task_mut(self.id))
+                .expect("The task was checked");
+            let unlinked: Vec<usize> = self
+                .blocked_by
+                .iter()
+                .copied()
+                .filter(|id| !task.blocked_by.contains(id))
+                .collect();
+            if !unlinked.is_empty() {
+                return Err(Error::Invalid(format!(
+                    "Task {} doesn't wait on: {}",
+                    self.id,
+                    utils::format_ids(&unlinked)
+                )));
+            }
+
+            task.blocked_by.retain(|id| !self.blocked_by.contains(id));
+            Ok(())
+        })?;
+
+        println!(
+            "{} set task {} in group `{}` to stop waiting on: {}",
+            fg_color!("Successfully", Green),
+            self.id,
+            fg_color!(self.group_name, Yellow),
+            utils::format_ids(&self.blocked_by)
+        );
+
+        Ok(())
+    }
+}
+
+/// A task reached by following dependency links as it appears in structured output
+#[derive(Serialize)]
+struct LinkView {
+    depth: usize,
+    #[serde(flatten)]
+    task: TaskView,
+}
+
+#[derive(Serialize)]
+struct DepsView {
+    task: TaskView,
+    upstream: Vec<LinkView>,
+    downstream: Vec<LinkView>,
+}
+
+/// A linked task as a single row of a table
+#[derive(Serialize)]
+struct LinkRow {
+    /// `upstream` for tasks that are waited on, `downstream` for tasks that wait
+    direction: &'static str,
+    depth: usize,
+    group: String,
+    id: usize,
+    text: String,
+    state: &'static str,
+}
+
+/// Views a task in whichever group it's in
+fn task_view(data: &Project, id: usize) -> TaskView {
+    let (group, task) = data.find_task(id).expect("Linked tasks exist");
+    TaskView {
+        parent: group.parent_task(id),
+        ..TaskView::new(&group.name, task, utils::today())
+    }
+}
+
+/// Prints linked tasks indented by how many links away they are
+fn display_links(title: &str, data: &Project, links: &[Link]) {
+    println!("  {}:", title);
+    if links.is_empty() {
+        println!("    Nothing");
+    }
+    for link in links {
+        let (group, task) = data.find_task(link.id).expect("Linked tasks exist");
+        println!(
+            "    {}[{}] {} - [{}] {}",
+            "    ".repeat(link.depth - 1),
+            fg_color!(group.name, Yellow),
+            task.id,
+            task.state.symbol(),
+            utils::format_task(task)
+        );
+    }
+    println!();
+}
+
+impl super::Command for TaskDepsArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        super::Report::report(self, file_name, Format::Text)
+    }
+}
+
+impl super::Report for TaskDepsArgs {
+    fn report(self, file_name: &str, format: Format) -> Result<(), Error> {
+        let data = utils::get_data(file_name)?;
+        check_task(&data, &self.group_name, self.id)?;
+
+        let upstream = data.upstream(self.id);
+        let downstream = data.downstream(self.id);
+
+        if !format.is_text() {
+            let views = |links: &[Link]| -> Vec<LinkView> {
+                links
+                    .iter()
+                    .map(|l| LinkView {
+                        depth: l.depth,
+                        task: task_view(&data, l.id),
+                    })
+                    .collect()
+            };
+            let view = DepsView {
+                task: task_view(&data, self.id),
+                upstream: views(&upstream),
+                downstream: views(&downstream),
+            };
+
+            let mut rows: Vec<LinkRow> = vec![];
+            for (direction, links) in [
+                ("upstream", &view.upstream),
+                ("downstream", &view.downstream),
+            ] {
+                rows.extend(links.iter().map(|l| LinkRow {
+                    direction,
+                    depth: l.depth,
+                    group: l.task.group.clone(),
+                    id: l.task.id,
+                    text: l.task.text.clone(),
+                    state: l.task.state,
+                }));
+            }
+            return output::print(format, &view, &rows);
         }
+
+        let (_, task) = data.find_task(self.id).expect("The task was checked");
+        println!(
+            "\n[{}] {} - [{}] {}\n",
+            fg_color!(self.group_name, Yellow),
+            task.id,
+            task.state.symbol(),
+            utils::format_task(task)
+        );
+        display_links("Waits on", &data, &upstream);
+        display_links("Blocking", &data, &downstream);
+
+        Ok(())
     }
 }
@@ -0,0 +1,353 @@
+use crate::data::{ItemKind, Project};
+use crate::{fg_color, utils, Error};
+use clap::builder::styling;
+use clap::{Args, Parser, Subcommand};
+
+#[derive(Subcommand)]
+pub enum Commands {
+    Move(Move),
+    Copy(Copy),
+}
+
+#[derive(Parser)]
+pub struct Move {
+    #[structopt(subcommand)]
+    pub move_commands: MoveCommands,
+}
+
+#[derive(Subcommand)]
+pub enum MoveCommands {
+    /// Move note(s) to another group
+    Note(MoveNoteArgs),
+    /// Move task(s) to another group
+    Task(MoveTaskArgs),
+}
+
+#[derive(Parser)]
+pub struct Copy {
+    #[structopt(subcommand)]
+    pub copy_commands: CopyCommands,
+}
+
+#[derive(Subcommand)]
+pub enum CopyCommands {
+    /// Copy note(s) to another group
+    Note(CopyNoteArgs),
+    /// Copy task(s) to another group
+    Task(CopyTaskArgs),
+}
+
+#[derive(Args, Default)]
+pub struct MoveNoteArgs {
+    /// The group that the note(s) are in
+    pub from_group: String,
+    /// The ID(s) of the note(s) you want to move
+    #[arg(required = true)]
+    pub ids: Vec<usize>,
+    /// The group that you will move the note(s) to
+    pub to_group: String,
+    /// Where to put the note(s) in the group, starting from 1 (they go last by default)
+    #[arg(short, long)]
+    pub position: Option<usize>,
+}
+
+#[derive(Args, Default)]
+pub struct MoveTaskArgs {
+    /// The group that the task(s) are in
+    pub from_group: String,
+    /// The ID(s) of the task(s) you want to move
+    #[arg(required = true)]
+    pub ids: Vec<usize>,
+    /// The group that you will move the task(s) to
+    pub to_group: String,
+    /// Where to put the task(s) in the group, starting from 1 (they go last by default)
+    #[arg(short, long)]
+    pub position: Option<usize>,
+}
+
+#[derive(Args, Default)]
+pub struct CopyNoteArgs {
+    /// The group that the note(s) are in
+    pub from_group: String,
+    /// The ID(s) of the note(s) you want to copy
+    #[arg(required = true)]
+    pub ids: Vec<usize>,
+    /// The group that you will copy the note(s) to
+    pub to_group: String,
+    /// Where to put the copies in the group, starting from 1 (they go last by default)
+    #[arg(short, long)]
+    pub position: Option<usize>,
+}
+
+#[derive(Args, Default)]
+pub struct CopyTaskArgs {
+    /// The group that the task(s) are in
+    pub from_group: String,
+    /// The ID(s) of the task(s) you want to copy
+    #[arg(required = true)]
+    pub ids: Vec<usize>,
+    /// The group that you will copy the task(s) to
+    pub to_group: String,
+    /// Where to put the copies in the group, starting from 1 (they go last by default)
+    #[arg(short, long)]
+    pub position: Option<usize>,
+}
+
+/// Takes the items with the specified IDs out of `items`, or copies them, keeping their order
+fn take<T: Clone>(
+    items: &mut Vec<T>,
+    ids: &[usize],
+    id_of: impl Fn(&T) -> usize,
+    copy: bool,
+) -> Vec<T> {
+    if copy {
+        return items
+            .iter()
+            .filter(|item| ids.contains(&id_of(item)))
+            .cloned()
+            .collect();
+    }
+
+    let (taken, kept) = items.drain(..).partition(|item| ids.contains(&id_of(item)));
+    *items = kept;
+    taken
+}
+
+/// Inserts `new_items` at a position starting from 1, or at the end if there's no position
+fn insert<T>(items: &mut Vec<T>, new_items: Vec<T>, position: Option<usize>) -> Result<(), Error> {
+    let index = match position {
+        None => items.len(),
+        Some(position) if position >= 1 && position <= items.len() + 1 => position - 1,
+        Some(position) => {
+            return Err(Error::Usage(format!(
+                "Position {} is out of range, the group has room for positions 1 to {}",
+                position,
+                items.len() + 1
+            )))
+        }
+    };
+
+    items.splice(index..index, new_items);
+    Ok(())
+}
+
+/// Moves or copies items between groups, copies get new IDs but keep everything else
+fn transfer(
+    data: &mut Project,
+    kind: ItemKind,
+    from_group: &str,
+    ids: &[usize],
+    to_group: &str,
+    position: Option<usize>,
+    copy: bool,
+) -> Result<Vec<usize>, Error> {
+    for group_name in [from_group, to_group] {
+        if !data.groups.contains_key(group_name) {
+            return Err(Error::Missing(format!(
+                "Specified group `{}` does not exist",
+                group_name
+            )));
+        }
+    }
+    if ids.is_empty() {
+        return Err(Error::Usage("No data ID specified".to_string()));
+    }
+
+    let mut from = data.get_group(from_group)?;
+    let invalid_ids: Vec<String> = ids
+        .iter()
+        .filter(|id| match kind {
+            ItemKind::Task => from.find_task(**id).is_none(),
+            ItemKind::Note => from.note_index(**id).is_none(),
+        })
+        .map(|id| id.to_string())
+        .collect();
+
+    if !invalid_ids.is_empty() {
+        return Err(Error::Missing(format!(
+            "Some given IDs do not exist in the group: {}",
+            invalid_ids.join(", ")
+        )));
+    }
+
+    // a sub-task only makes sense under its parent, it goes wherever the parent goes
+    if let ItemKind::Task = kind {
+        let subtask_ids: Vec<usize> = ids
+            .iter()
+            .copied()
+            .filter(|id| from.task_index(*id).is_none())
+            .collect();
+        if !subtask_ids.is_empty() {
+            return Err(Error::Invalid(format!(
+                "Sub-tasks can't be {} on their own, {} the parent task instead: {}",
+                if copy { "copied" } else { "moved" },
+                if copy { "copy" } else { "move" },
+                utils::format_ids(&subtask_ids)
+            )));
+        }
+    }
+
+    let mut new_ids: Vec<usize> = vec![];
+    match kind {
+        ItemKind::Task => {
+            let mut tasks = take(&mut from.tasks, ids, |t| t.id, copy);
+            if copy {
+                // copies of sub-tasks need new IDs too
+                for task in &mut tasks {
+                    task.for_each_mut(&mut |t| t.id = data.next_task_id());
+                }
+            }
+            new_ids.extend(tasks.iter().map(|t| t.id));
+
+            // the source is saved first, so moving inside one group works on the updated items
+            data.groups.insert(from_group.to_string(), from);
+            let mut to = data.get_group(to_group)?;
+            insert(&mut to.tasks, tasks, position)?;
+            data.groups.insert(to_group.to_string(), to);
+        }
+        ItemKind::Note => {
+            let mut notes = take(&mut from.notes, ids, |n| n.id, copy);
+            if copy {
+                for note in &mut notes {
+                    note.id = data.next_note_id();
+                }
+            }
+            new_ids.extend(notes.iter().map(|n| n.id));
+
+            data.groups.insert(from_group.to_string(), from);
+            let mut to = data.get_group(to_group)?;
+            insert(&mut to.notes, notes, position)?;
+            data.groups.insert(to_group.to_string(), to);
+        }
+    }
+
+    Ok(new_ids)
+}
+
+impl super::Command for MoveNoteArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = format!(
+            "move note(s) {} from group {} to {}",
+            utils::format_ids(&self.ids),
+            self.from_group,
+            self.to_group
+        );
+        let ids = utils::update_data(file_name, &summary, |data| {
+            transfer(
+                data,
+                ItemKind::Note,
+                &self.from_group,
+                &self.ids,
+                &self.to_group,
+                self.position,
+                false,
+            )
+        })?;
+
+        println!(
+            "Moved note(s) from group `{}` to `{}` {}: {}",
+            fg_color!(self.from_group, Yellow),
+            fg_color!(self.to_group, Yellow),
+            fg_color!("successfully", Green),
+            utils::format_ids(&ids)
+        );
+
+        Ok(())
+    }
+}
+
+impl super::Command for MoveTaskArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = format!(
+            "move task(s) {} from group {} to {}",
+            utils::format_ids(&self.ids),
+            self.from_group,
+            self.to_group
+        );
+        let ids = utils::update_data(file_name, &summary, |data| {
+            transfer(
+                data,
+                ItemKind::Task,
+                &self.from_group,
+                &self.ids,
+                &self.to_group,
+                self.position,
+                false,
+            )
+        })?;
+
+        println!(
+            "Moved task(s) from group `{}` to `{}` {}: {}",
+            fg_color!(self.from_group, Yellow),
+            fg_color!(self.to_group, Yellow),
+            fg_color!("successfully", Green),
+            utils::format_ids(&ids)
+        );
+
+        Ok(())
+    }
+}
+
+impl super::Command for CopyNoteArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = format!(
+            "copy note(s) {} from group {} to {}",
+            utils::format_ids(&self.ids),
+            self.from_group,
+            self.to_group
+        );
+        let ids = utils::update_data(file_name, &summary, |data| {
+            transfer(
+                data,
+                ItemKind::Note,
+                &self.from_group,
+                &self.ids,
+                &self.to_group,
+                self.position,
+                true,
+            )
+        })?;
+
+        println!(
+            "Copied note(s) from group `{}` to `{}` {}, the copies have ID(s): {}",
+            fg_color!(self.from_group, Yellow),
+            fg_color!(self.to_group, Yellow),
+            fg_color!("successfully", Green),
+            utils::format_ids(&ids)
+        );
+
+        Ok(())
+    }
+}
+
+impl super::Command for CopyTaskArgs {
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        let summary = format!(
+            "copy task(s) {} from group {} to {}",
+            utils::format_ids(&self.ids),
+            self.from_group,
+            self.to_group
+        );
+        let ids = utils::update_data(file_name, &summary, |data| {
+            transfer(
+                data,
+                ItemKind::Task,
+                &self.from_group,
+                &self.ids,
+                &self.to_group,
+                self.position,
+                true,
+            )
+        })?;
+
+        println!(
+            "Copied task(s) from group `{}` to `{}` {}, the copies have ID(s): {}",
+            fg_color!(self.from_group, Yellow),
+            fg_color!(self.to_group, Yellow),
+            fg_color!("successfully", Green),
+            utils::format_ids(&ids)
+        );
+
+        Ok(())
+    }
+}
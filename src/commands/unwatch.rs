@@ -1,110 +1,118 @@
-use clap::{error::ErrorKind, Args, CommandFactory};
+use clap::{ArgGroup, Args};
 use clap::builder::styling;
 
-use crate::{fg_color, utils, Cli};
+use crate::{fg_color, utils, Error};
 
 #[derive(Args)]
+// only --all asks before unwatching, so the confirmation flags don't mean anything without it
+#[command(group(
+    ArgGroup::new("confirm_all")
+        .args(["yes", "dry_run"])
+        .multiple(true)
+        .requires("all")
+))]
 pub struct UnwatchArgs {
     /// The name(s) of the group(s) that you want to unwatch
     pub group_names: Vec<String>,
     /// Use this flag to unwatch all watched group (ignotes GROUP_NAMES)
     #[arg(short, long)]
     pub all: bool,
+    #[command(flatten)]
+    pub confirmation: super::Confirmation,
 }
 
 impl super::Command for UnwatchArgs {
-    fn run(self, file_name: &str) {
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
-        };
+    fn run(self, file_name: &str) -> Result<(), Error> {
+        if self.all {
+            let data = utils::get_data(file_name)?;
+            if data.active_groups.is_empty() {
+                return Err(Error::Invalid("No groups are being watched".to_string()));
+            }
 
-        let mut to_unwatch: Vec<String> = vec![];
+            println!("The following group(s) will be unwatched:\n");
+            for group in &data.active_groups {
+                println!("    {}", fg_color!(group, Yellow));
+            }
+            println!();
 
-        if self.all && !data.active_groups.is_empty() {
-            to_unwatch = data.active_groups.clone();
-        } else if self.all {
-            let _ = Cli::command()
-                .error(ErrorKind::Io, "No groups are being watched")
-                .print();
-            return;
-        } else {
-            if self.group_names.is_empty() {
-                let _ = Cli::command()
-                    .error(
-                        ErrorKind::MissingRequiredArgument,
-                        "No groups specified to unwatch",
-                    )
-                    .print();
-                return;
+            if !self.confirmation.proceed("Unwatch all groups?", "unwatched")? {
+                return Ok(());
             }
+        }
 
-            let mut unwatched_groups: Vec<&str> = vec![];
-            let mut undefined_groups: Vec<&str> = vec![];
-            for group in &self.group_names {
-                if !data.groups.contains_key(group) {
-                    undefined_groups.push(group);
-                } else if !data.active_groups.contains(group) {
-                    unwatched_groups.push(group);
+        let summary = if self.all {
+            "unwatch all groups".to_string()
+        } else {
+            format!("unwatch group(s) {}", self.group_names.join(", "))
+        };
+        let unwatched = utils::update_data(file_name, &summary, |data| {
+            let mut to_unwatch: Vec<String> = vec![];
+
+            if self.all && !data.active_groups.is_empty() {
+                to_unwatch = data.active_groups.clone();
+            } else if self.all {
+                return Err(Error::Invalid("No groups are being watched".to_string()));
+            } else {
+                if self.group_names.is_empty() {
+                    return Err(Error::Usage("No groups specified to unwatch".to_string()));
                 }
-            }
 
-            if !unwatched_groups.is_empty() && !undefined_groups.is_empty() {
-                let _ = Cli::command()
-                    .error(
-                        ErrorKind::ValueValidation,
-                        format!("Following groups are already not watched: {},\nFollowing groups are not created: {}", unwatched_groups.join(", "), undefined_groups.join(", ")),
-                    )
-                    .print();
-                return;
-            } else if !unwatched_groups.is_empty() {
-                let _ = Cli::command()
-                    .error(
-                        ErrorKind::ValueValidation,
-                        format!(
-                            "Following groups are already not watched: {}",
-                            unwatched_groups.join(", ")
-                        ),
-                    )
-                    .print();
-                return;
-            } else if !undefined_groups.is_empty() {
-                let _ = Cli::command()
-                    .error(
-                        ErrorKind::ValueValidation,
-                        format!(
-                            "Following groups are not created: {}",
-                            undefined_groups.join(", ")
-                        ),
-                    )
-                    .print();
-                return;
-            }
+                let mut unwatched_groups: Vec<&str> = vec![];
+                let mut undefined_groups: Vec<&str> = vec![];
+                for group in &self.group_names {
+                    if !data.groups.contains_key(group) {
+                        undefined_groups.push(group);
+                    } else if !data.active_groups.contains(group) {
+                        unwatched_groups.push(group);
+                    }
+                }
 
-            for group in &self.group_names {
-                to_unwatch.push(group.to_string());
-                to_unwatch.append(&mut data.get_group_descendants(group));
+                if !unwatched_groups.is_empty() && !undefined_groups.is_empty() {
+                    return Err(Error::Missing(format!(
+                        "Following groups are already not watched: {},\nFollowing groups are not created: {}",
+                        unwatched_groups.join(", "),
+                        undefined_groups.join(", ")
+                    )));
+                } else if !unwatched_groups.is_empty() {
+                    return Err(Error::Invalid(format!(
+                        "Following groups are already not watched: {}",
+                        unwatched_groups.join(", ")
+                    )));
+                } else if !undefined_groups.is_empty() {
+                    return Err(Error::Missing(format!(
+                        "Following groups are not created: {}",
+                        undefined_groups.join(", ")
+                    )));
+                }
+
+                for group in &self.group_names {
+                    to_unwatch.push(group.to_string());
+                    to_unwatch.append(&mut data.get_group_descendants(group));
+                }
             }
-        }
 
-        let unwatched = to_unwatch.clone();
+            let unwatched = to_unwatch.clone();
 
-        while !to_unwatch.is_empty() {
-            if data.active_groups.contains(&to_unwatch[0]) {
-                let index = data
-                    .active_groups
-                    .iter()
-                    .position(|g| g == to_unwatch[0].as_str())
-                    .expect("Group specified to be unwatched was not found in watched groups");
-                data.active_groups.remove(index);
+            while !to_unwatch.is_empty() {
+                if data.active_groups.contains(&to_unwatch[0]) {
+                    let index = data
+                        .active_groups
+                        .iter()
+                        .position(|g| g == to_unwatch[0].as_str())
+                        .expect("Group specified to be unwatched was not found in watched groups");
+                    data.active_groups.remove(index);
+                }
+                to_unwatch.remove(0);
             }
-            to_unwatch.remove(0);
-        }
-        utils::write_data(file_name, &data);
+            Ok(unwatched)
+        })?;
 
         println!(
             "Unwatched group(s) {}: {}",
             fg_color!("successfully", Green),
             unwatched.join(", ")
         );
+
+        Ok(())
     }
 }
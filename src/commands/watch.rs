@@ -1,87 +1,91 @@
 use clap::builder::styling;
-use clap::{error::ErrorKind, Args, CommandFactory};
+use clap::Args;
 
-use crate::{fg_color, utils, Cli};
+use crate::{fg_color, utils, Error};
 
 #[derive(Args)]
 pub struct WatchArgs {
     /// The name(s) of the group(s) that you want to watch
     pub group_names: Vec<String>,
+    /// Also watch archived groups and sub-groups
+    #[arg(long)]
+    pub include_archived: bool,
 }
 
 impl super::Command for WatchArgs {
-    fn run(self, file_name: &str) {
+    fn run(self, file_name: &str) -> Result<(), Error> {
         if self.group_names.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::MissingRequiredArgument,
-                    "No groups specified to be watched",
-                )
-                .print();
-            return;
+            return Err(Error::Usage("No groups specified to be watched".to_string()));
         }
 
-        let Some(mut data) = utils::get_data(file_name) else {
-            return;
-        };
-        let mut already_active: Vec<&str> = vec![];
-        let mut undefined_groups: Vec<&str> = vec![];
-        for group in &self.group_names {
-            if !data.groups.contains_key(group) {
-                undefined_groups.push(group);
-            } else if data.active_groups.contains(group) {
-                already_active.push(group);
+        let summary = format!("watch group(s) {}", self.group_names.join(", "));
+        utils::update_data(file_name, &summary, |data| {
+            let mut already_active: Vec<&str> = vec![];
+            let mut undefined_groups: Vec<&str> = vec![];
+            for group in &self.group_names {
+                if !data.groups.contains_key(group) {
+                    undefined_groups.push(group);
+                } else if data.active_groups.contains(group) {
+                    already_active.push(group);
+                }
             }
-        }
 
-        if !already_active.is_empty() && !undefined_groups.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::ValueValidation,
-                    format!("Following groups are already watched: {},\nFollowing groups are not created: {}", already_active.join(", "), undefined_groups.join(", ")),
-                )
-                .print();
-            return;
-        } else if !already_active.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::ValueValidation,
-                    format!(
-                        "Following groups are already watched: {}",
-                        already_active.join(", ")
-                    ),
-                )
-                .print();
-            return;
-        } else if !undefined_groups.is_empty() {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::ValueValidation,
-                    format!(
-                        "Following groups are not created: {}",
-                        undefined_groups.join(", ")
-                    ),
-                )
-                .print();
-            return;
-        }
+            if !already_active.is_empty() && !undefined_groups.is_empty() {
+                return Err(Error::Missing(format!(
+                    "Following groups are already watched: {},\nFollowing groups are not created: {}",
+                    already_active.join(", "),
+                    undefined_groups.join(", ")
+                )));
+            } else if !already_active.is_empty() {
+                return Err(Error::Invalid(format!(
+                    "Following groups are already watched: {}",
+                    already_active.join(", ")
+                )));
+            } else if !undefined_groups.is_empty() {
+                return Err(Error::Missing(format!(
+                    "Following groups are not created: {}",
+                    undefined_groups.join(", ")
+                )));
+            }
 
-        let mut to_select: Vec<String> = vec![];
+            if !self.include_archived {
+                let archived: Vec<&str> = self
+                    .group_names
+                    .iter()
+                    .filter(|g| data.is_archived(g))
+                    .map(|g| g.as_str())
+                    .collect();
+                if !archived.is_empty() {
+                    return Err(Error::Invalid(format!(
+                        "Following groups are archived: {} (Use --include-archived flag to watch them)",
+                        archived.join(", ")
+                    )));
+                }
+            }
 
-        for group in &self.group_names {
-            to_select.push(group.to_string());
-            to_select.append(&mut data.get_group_descendants(group));
-        }
+            let mut to_select: Vec<String> = vec![];
 
-        data.active_groups.append(&mut to_select);
-        data.active_groups.sort();
-        data.active_groups.dedup();
-        utils::write_data(file_name, &data);
+            for group in &self.group_names {
+                to_select.push(group.to_string());
+                to_select.append(&mut data.get_group_descendants(group));
+            }
+            // archived sub-groups stay hidden when their parent is watched
+            if !self.include_archived {
+                to_select.retain(|g| !data.is_archived(g));
+            }
+
+            data.active_groups.append(&mut to_select);
+            data.active_groups.sort();
+            data.active_groups.dedup();
+            Ok(())
+        })?;
 
         println!(
             "Added group(s) to be watched {}: {}",
             fg_color!("successfully", Green),
             self.group_names.join(", ")
         );
+
+        Ok(())
     }
 }
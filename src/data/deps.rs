@@ -0,0 +1,108 @@
+use super::{Group, Project, Task};
+use std::collections::HashSet;
+
+/// A task reached by following dependency links, and how many links away it is
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Link {
+    pub id: usize,
+    pub depth: usize,
+}
+
+impl Project {
+    /// Finds a task or sub-task in any group, along with the group it's in
+    pub fn find_task(&self, id: usize) -> Option<(&Group, &Task)> {
+        self.groups
+            .values()
+            .find_map(|g| g.find_task(id).map(|t| (g, t)))
+    }
+
+    /// The tasks that a task waits on and that aren't finished yet
+    ///
+    /// Blockers that don't exist anymore aren't waited on.
+    pub fn unfinished_blockers(&self, task: &Task) -> Vec<usize> {
+        task.blocked_by
+            .iter()
+            .copied()
+            .filter(|id| {
+                self.find_task(*id)
+                    .is_some_and(|(_, t)| !t.state.is_closed())
+            })
+            .collect()
+    }
+
+    /// The tasks that wait on the task with the specified ID, lowest ID first
+    pub fn dependents(&self, id: usize) -> Vec<usize> {
+        let mut dependents: Vec<usize> = self
+            .groups
+            .values()
+            .flat_map(|g| g.all_tasks())
+            .filter(|t| t.blocked_by.contains(&id))
+            .map(|t| t.id)
+            .collect();
+        dependents.sort();
+        dependents
+    }
+
+    /// Every task that the task waits on, directly or through other tasks
+    pub fn upstream(&self, id: usize) -> Vec<Link> {
+        self.chain(id, |id| {
+            self.find_task(id)
+                .map(|(_, t)| t.blocked_by.clone())
+                .unwrap_or_default()
+        })
+    }
+
+    /// Every task that waits on the task, directly or through other tasks
+    pub fn downstream(&self, id: usize) -> Vec<Link> {
+        self.chain(id, |id| self.dependents(id))
+    }
+
+    /// Whether making the task wait on `blocker` would make a task wait on itself
+    pub fn would_cycle(&self, id: usize, blocker: usize) -> bool {
+        id == blocker || self.upstream(blocker).iter().any(|l| l.id == id)
+    }
+
+    /// Removes links to tasks that don't exist anymore, returns how many were removed
+    pub fn drop_missing_links(&mut self) -> usize {
+        let ids: HashSet<usize> = self
+            .groups
+            .values()
+            .flat_map(|g| g.all_tasks())
+            .map(|t| t.id)
+            .collect();
+
+        let mut removed = 0;
+        for group in self.groups.values_mut() {
+            group.for_each_task_mut(&mut |task| {
+                let before = task.blocked_by.len();
+                task.blocked_by.retain(|id| ids.contains(id));
+                removed += before - task.blocked_by.len();
+            });
+        }
+        removed
+    }
+
+    /// Follows links depth first, each task is visited once even if the links loop
+    fn chain(&self, id: usize, next: impl Fn(usize) -> Vec<usize>) -> Vec<Link> {
+        let mut links: Vec<Link> = vec![];
+        let mut visited: HashSet<usize> = HashSet::from([id]);
+        let mut stack: Vec<Link> = next(id)
+            .into_iter()
+            .rev()
+            .map(|id| Link { id, depth: 1 })
+            .collect();
+
+        while let Some(link) = stack.pop() {
+            if !visited.insert(link.id) || self.find_task(link.id).is_none() {
+                continue;
+            }
+            links.push(link);
+            stack.extend(next(link.id).into_iter().rev().map(|id| Link {
+                id,
+                depth: link.depth + 1,
+            }));
+        }
+
+        links
+    }
+}
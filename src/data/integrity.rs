@@ -0,0 +1,489 @@
+use super::{Group, Project};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+/// Something in the project data that breaks the links between groups or items
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Problem {
+    /// A group is stored under a key that isn't its name
+    NameMismatch { key: String, name: String },
+    /// A group lists a child group that doesn't exist
+    MissingChild { parent: String, child: String },
+    /// A group lists the same child more than once
+    DuplicateChild { parent: String, child: String },
+    /// A group is listed as a child of more than one group
+    MultipleParents { child: String, parents: Vec<String> },
+    /// Groups are each other's ancestors, so they can't be reached from the top level
+    Cycle { groups: Vec<String> },
+    /// A watched group doesn't exist
+    MissingWatched { group: String },
+    /// A group is watched more than once
+    DuplicateWatched { group: String },
+    /// Two or more tasks share an ID
+    DuplicateTaskId { id: usize },
+    /// Two or more notes share an ID
+    DuplicateNoteId { id: usize },
+    /// The last given task ID is lower than an existing task's ID, so it would be given again
+    TaskCounterBehind { last: usize, highest: usize },
+    /// The last given note ID is lower than an existing note's ID, so it would be given again
+    NoteCounterBehind { last: usize, highest: usize },
+    /// A task waits on a task that doesn't exist
+    MissingBlocker { id: usize, blocker: usize },
+    /// Tasks wait on each other, so none of them can be finished first
+    DependencyCycle { tasks: Vec<usize> },
+}
+
+impl fmt::Display for Problem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Problem::NameMismatch { key, name } => {
+                write!(f, "Group `{}` is stored as `{}`", name, key)
+            }
+            Problem::MissingChild { parent, child } => write!(
+                f,
+                "Group `{}` has a sub-group `{}` that does not exist",
+                parent, child
+            ),
+            Problem::DuplicateChild { parent, child } => write!(
+                f,
+                "Group `{}` lists its sub-group `{}` more than once",
+                parent, child
+            ),
+            Problem::MultipleParents { child, parents } => write!(
+                f,
+                "Group `{}` is a sub-group of more than one group: {}",
+                child,
+                parents.join(", ")
+            ),
+            Problem::Cycle { groups } => write!(
+                f,
+                "Groups are sub-groups of each other: {} -> {}",
+                groups.join(" -> "),
+                groups[0]
+            ),
+            Problem::MissingWatched { group } => {
+                write!(f, "Watched group `{}` does not exist", group)
+            }
+            Problem::DuplicateWatched { group } => {
+                write!(f, "Group `{}` is watched more than once", group)
+            }
+            Problem::DuplicateTaskId { id } => write!(f, "More than one task has ID {}", id),
+            Problem::DuplicateNoteId { id } => write!(f, "More than one note has ID {}", id),
+            Problem::TaskCounterBehind { last, highest } => write!(
+                f,
+                "The last given task ID is {}, but a task has ID {}",
+                last, highest
+            ),
+            Problem::NoteCounterBehind { last, highest } => write!(
+                f,
+                "The last given note ID is {}, but a note has ID {}",
+                last, highest
+            ),
+            Problem::MissingBlocker { id, blocker } => write!(
+                f,
+                "Task {} waits on task {}, which does not exist",
+                id, blocker
+            ),
+            Problem::DependencyCycle { tasks } => write!(
+                f,
+                "Tasks wait on each other: {} -> {}",
+                tasks
+                    .iter()
+                    .map(|id| id.to_string())
+                    .collect::<Vec<_>>()
+                    .join(" -> "),
+                tasks[0]
+            ),
+        }
+    }
+}
+
+impl Project {
+    /// Finds problems in how groups link to each other, which would break walking the group tree
+    ///
+    /// This only looks at groups, not at their items, so it's cheap enough to do on every load.
+    pub fn check_groups(&self) -> Vec<Problem> {
+        let mut problems: Vec<Problem> = vec![];
+        let mut parents: HashMap<&str, Vec<String>> = HashMap::new();
+
+        for key in sorted_keys(self) {
+            let group = &self.groups[key];
+            if group.name != *key {
+                problems.push(Problem::NameMismatch {
+                    key: key.to_string(),
+                    name: group.name.clone(),
+                });
+            }
+
+            let mut seen: HashSet<&str> = HashSet::new();
+            for child in &group.groups {
+                if !seen.insert(child) {
+                    problems.push(Problem::DuplicateChild {
+                        parent: key.to_string(),
+                        child: child.to_string(),
+                    });
+                } else if !self.groups.contains_key(child) {
+                    problems.push(Problem::MissingChild {
+                        parent: key.to_string(),
+                        child: child.to_string(),
+                    });
+                } else {
+                    parents.entry(child).or_default().push(key.to_string());
+                }
+            }
+        }
+
+        let mut multiple_parents: Vec<Problem> = parents
+            .iter()
+            .filter(|(_, parents)| parents.len() > 1)
+            .map(|(child, parents)| Problem::MultipleParents {
+                child: child.to_string(),
+                parents: parents.clone(),
+            })
+            .collect();
+        multiple_parents.sort_by_key(|p| p.to_string());
+        problems.append(&mut multiple_parents);
+
+        for groups in find_cycles(self, &parents) {
+            problems.push(Problem::Cycle { groups });
+        }
+
+        let mut watched: HashSet<&str> = HashSet::new();
+        for group in &self.active_groups {
+            if !self.groups.contains_key(group) {
+                problems.push(Problem::MissingWatched {
+                    group: group.to_string(),
+                });
+            } else if !watched.insert(group) {
+                problems.push(Problem::DuplicateWatched {
+                    group: group.to_string(),
+                });
+            }
+        }
+
+        problems
+    }
+
+    /// Finds every problem in the project, including ones with the IDs of tasks and notes
+    pub fn check(&self) -> Vec<Problem> {
+        let mut problems = self.check_groups();
+
+        let mut task_ids: HashMap<usize, usize> = HashMap::new();
+        let mut note_ids: HashMap<usize, usize> = HashMap::new();
+        for group in self.groups.values() {
+            for task in group.all_tasks() {
+                *task_ids.entry(task.id).or_default() += 1;
+            }
+            for note in &group.notes {
+                *note_ids.entry(note.id).or_default() += 1;
+            }
+        }
+
+        let mut duplicate_tasks: Vec<usize> = task_ids
+            .iter()
+            .filter(|(_, count)| **count > 1)
+            .map(|(id, _)| *id)
+            .collect();
+        duplicate_tasks.sort();
+        problems.extend(
+            duplicate_tasks
+                .into_iter()
+                .map(|id| Problem::DuplicateTaskId { id }),
+        );
+
+        let mut duplicate_notes: Vec<usize> = note_ids
+            .iter()
+            .filter(|(_, count)| **count > 1)
+            .map(|(id, _)| *id)
+            .collect();
+        duplicate_notes.sort();
+        problems.extend(
+            duplicate_notes
+                .into_iter()
+                .map(|id| Problem::DuplicateNoteId { id }),
+        );
+
+        let highest_task = task_ids.keys().copied().max().unwrap_or(0);
+        if highest_task > self.last_task_id {
+            problems.push(Problem::TaskCounterBehind {
+                last: self.last_task_id,
+                highest: highest_task,
+            });
+        }
+        let highest_note = note_ids.keys().copied().max().unwrap_or(0);
+        if highest_note > self.last_note_id {
+            problems.push(Problem::NoteCounterBehind {
+                last: self.last_note_id,
+                highest: highest_note,
+            });
+        }
+
+        let mut blocked_by: HashMap<usize, &Vec<usize>> = HashMap::new();
+        for task in self.groups.values().flat_map(|g| g.all_tasks()) {
+            blocked_by.entry(task.id).or_insert(&task.blocked_by);
+        }
+        let mut ids: Vec<usize> = blocked_by.keys().copied().collect();
+        ids.sort();
+        for id in &ids {
+            for blocker in blocked_by[id].iter() {
+                if !blocked_by.contains_key(blocker) {
+                    problems.push(Problem::MissingBlocker {
+                        id: *id,
+                        blocker: *blocker,
+                    });
+                }
+            }
+        }
+        for tasks in find_dependency_cycles(&ids, &blocked_by) {
+            problems.push(Problem::DependencyCycle { tasks });
+        }
+
+        problems
+    }
+
+    /// Repairs every problem that `check` finds, returns the problems that were repaired
+    ///
+    /// Repairs never delete tasks or notes. Groups that lose their parent are moved to the top level.
+    pub fn repair(&mut self) -> Vec<Problem> {
+        let mut repaired: Vec<Problem> = vec![];
+
+        // fixing one problem can uncover another, like a cycle hidden behind a second parent
+        loop {
+            let problems = self.check();
+            if problems.is_empty() {
+                break;
+            }
+
+            for problem in &problems {
+                self.repair_one(problem);
+            }
+            repaired.extend(problems);
+        }
+
+        repaired
+    }
+
+    fn repair_one(&mut self, problem: &Problem) {
+        match problem {
+            Problem::NameMismatch { key, .. } => {
+                if let Some(group) = self.groups.get_mut(key) {
+                    group.name = key.to_string();
+                }
+            }
+            Problem::MissingChild { parent, child } => {
+                if let Some(group) = self.groups.get_mut(parent) {
+                    group.groups.retain(|c| c != child);
+                }
+            }
+            Problem::DuplicateChild { parent, child } => {
+                if let Some(group) = self.groups.get_mut(parent) {
+                    keep_first(&mut group.groups, child);
+                }
+            }
+            // the first parent in name order keeps the group
+            Problem::MultipleParents { child, parents } => {
+                for parent in parents.iter().skip(1) {
+                    if let Some(group) = self.groups.get_mut(parent) {
+                        group.groups.retain(|c| c != child);
+                    }
+                }
+            }
+            // the group with the lowest name is moved to the top level, which breaks the cycle
+            Problem::Cycle { groups } => {
+                let index = groups.len() - 1;
+                if let Some(group) = self.groups.get_mut(&groups[index]) {
+                    group.groups.retain(|c| *c != groups[0]);
+                }
+            }
+            Problem::MissingWatched { group } => {
+                self.active_groups.retain(|g| g != group);
+            }
+            Problem::DuplicateWatched { group } => {
+                keep_first(&mut self.active_groups, group);
+            }
+            // the first task with the ID keeps it, the others get new IDs
+            Problem::DuplicateTaskId { id } => {
+                let highest = self
+                    .groups
+                    .values()
+                    .flat_map(|g| g.all_tasks())
+                    .map(|t| t.id)
+                    .max();
+                self.last_task_id = self.last_task_id.max(highest.unwrap_or(0));
+
+                let positions =
+                    positions(self, |g| g.all_tasks().iter().map(|t| t.id).collect(), *id);
+                for (key, index) in positions.into_iter().skip(1) {
+                    let new_id = self.next_task_id();
+                    if let Some(group) = self.groups.get_mut(&key) {
+                        // positions of tasks follow `all_tasks`, and so does `for_each_task_mut`
+                        let mut current = 0;
+                        group.for_each_task_mut(&mut |task| {
+                            if current == index {
+                                task.id = new_id;
+                            }
+                            current += 1;
+                        });
+                    }
+                }
+            }
+            Problem::DuplicateNoteId { id } => {
+                let highest = self
+                    .groups
+                    .values()
+                    .flat_map(|g| &g.notes)
+                    .map(|n| n.id)
+                    .max();
+                self.last_note_id = self.last_note_id.max(highest.unwrap_or(0));
+
+                let positions = positions(self, |g| g.notes.iter().map(|n| n.id).collect(), *id);
+                for (key, index) in positions.into_iter().skip(1) {
+                    let new_id = self.next_note_id();
+                    if let Some(group) = self.groups.get_mut(&key) {
+                        group.notes[index].id = new_id;
+                    }
+                }
+            }
+            Problem::TaskCounterBehind { highest, .. } => {
+                self.last_task_id = self.last_task_id.max(*highest);
+            }
+            Problem::NoteCounterBehind { highest, .. } => {
+                self.last_note_id = self.last_note_id.max(*highest);
+            }
+            Problem::MissingBlocker { id, blocker } => self.unlink(*id, *blocker),
+            // the link back to the lowest ID is removed, which breaks the cycle
+            Problem::DependencyCycle { tasks } => self.unlink(tasks[tasks.len() - 1], tasks[0]),
+        }
+    }
+
+    /// Stops a task from waiting on `blocker`
+    fn unlink(&mut self, id: usize, blocker: usize) {
+        if let Some(task) = self.groups.values_mut().find_map(|g| g.find_task_mut(id)) {
+            task.blocked_by.retain(|b| *b != blocker);
+        }
+    }
+}
+
+/// Removes every copy of `item` from `items` except the first one
+fn keep_first(items: &mut Vec<String>, item: &str) {
+    let mut found = false;
+    items.retain(|i| {
+        if i != item {
+            return true;
+        }
+        let keep = !found;
+        found = true;
+        keep
+    });
+}
+
+/// Where the items with an ID are, as group names and indexes, in group name order
+fn positions(
+    project: &Project,
+    ids_of: impl Fn(&Group) -> Vec<usize>,
+    id: usize,
+) -> Vec<(String, usize)> {
+    let mut positions: Vec<(String, usize)> = vec![];
+    for key in sorted_keys(project) {
+        for (index, item_id) in ids_of(&project.groups[key]).into_iter().enumerate() {
+            if item_id == id {
+                positions.push((key.to_string(), index));
+            }
+        }
+    }
+    positions
+}
+
+fn sorted_keys(project: &Project) -> Vec<&String> {
+    let mut keys: Vec<&String> = project.groups.keys().collect();
+    keys.sort();
+    keys
+}
+
+/// Finds groups that are their own ancestors, each cycle starts at its lowest name
+fn find_cycles(project: &Project, parents: &HashMap<&str, Vec<String>>) -> Vec<Vec<String>> {
+    let mut cycles: Vec<Vec<String>> = vec![];
+    let mut checked: HashSet<String> = HashSet::new();
+
+    for key in sorted_keys(project) {
+        let mut path: Vec<String> = vec![];
+        let mut current = key.to_string();
+
+        // follows the first parent up, until the top level, a checked group, or a repeat
+        loop {
+            if checked.contains(&current) {
+                break;
+            }
+            if let Some(start) = path.iter().position(|g| *g == current) {
+                let mut cycle: Vec<String> = path[start..].to_vec();
+                // path goes from child to parent, cycles are shown from parent to child
+                cycle.reverse();
+                let lowest = cycle
+                    .iter()
+                    .enumerate()
+                    .min_by_key(|(_, g)| g.to_string())
+                    .map(|(i, _)| i)
+                    .unwrap_or(0);
+                cycle.rotate_left(lowest);
+                cycles.push(cycle);
+                break;
+            }
+
+            path.push(current.clone());
+            match parents.get(current.as_str()).and_then(|p| p.first()) {
+                Some(parent) => current = parent.clone(),
+                None => break,
+            }
+        }
+
+        checked.extend(path);
+    }
+
+    cycles
+}
+
+/// Finds tasks that wait on themselves through their blockers, each cycle starts at its lowest ID
+/// and every task in it waits on the next one
+fn find_dependency_cycles(
+    ids: &[usize],
+    blocked_by: &HashMap<usize, &Vec<usize>>,
+) -> Vec<Vec<usize>> {
+    let mut cycles: Vec<Vec<usize>> = vec![];
+    let mut checked: HashSet<usize> = HashSet::new();
+
+    for id in ids {
+        // each entry is a task on the current path and the index of the next blocker to follow
+        let mut path: Vec<(usize, usize)> = vec![(*id, 0)];
+
+        while let Some(&(current, next)) = path.last() {
+            let blockers = blocked_by.get(&current).map_or(&[][..], |b| b.as_slice());
+            if checked.contains(&current) || next >= blockers.len() {
+                checked.insert(current);
+                path.pop();
+                continue;
+            }
+
+            let index = path.len() - 1;
+            path[index].1 += 1;
+            let blocker = blockers[next];
+            match path.iter().position(|(id, _)| *id == blocker) {
+                Some(start) => {
+                    let mut cycle: Vec<usize> = path[start..].iter().map(|(id, _)| *id).collect();
+                    let lowest = cycle
+                        .iter()
+                        .enumerate()
+                        .min_by_key(|(_, id)| **id)
+                        .map(|(i, _)| i)
+                        .unwrap_or(0);
+                    cycle.rotate_left(lowest);
+                    if !cycles.contains(&cycle) {
+                        cycles.push(cycle);
+                    }
+                }
+                None => path.push((blocker, 0)),
+            }
+        }
+    }
+
+    cycles
+}
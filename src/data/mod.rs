@@ -1,15 +1,36 @@
-use std::collections::HashMap;
+mod deps;
+mod integrity;
 
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::ValueEnum;
 use serde::{Serialize, Deserialize};
+use std::fmt;
+
+use crate::Error;
+pub use deps::Link;
+pub use integrity::Problem;
+
+/// The version of the project data format, bumped whenever a migration is added
+pub const SCHEMA_VERSION: u32 = 2;
 
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct Project {
+    #[serde(default)]
+    pub schema_version: u32,
     pub information: ProjectInformation,
     pub active_groups: Vec<String>,
     pub groups: HashMap<String, Group>,
+    /// The last ID that was given to a task, IDs are never reused
+    #[serde(default)]
+    pub last_task_id: usize,
+    /// The last ID that was given to a note, IDs are never reused
+    #[serde(default)]
+    pub last_note_id: usize,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct ProjectInformation {
     pub title: Option<String>,
     pub description: Option<String>,
@@ -22,68 +43,227 @@ pub struct Group {
     pub notes: Vec<Note>,
     pub tasks: Vec<Task>,
     pub groups: Vec<String>,
+    /// Archived groups and their sub-groups are hidden unless they're asked for
+    #[serde(default)]
+    pub archived: bool,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct Note {
+    /// Persistent ID of the note, `0` means that no ID was assigned yet
+    #[serde(default)]
+    pub id: usize,
     pub note: String,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub tags: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub struct Task {
+    /// Persistent ID of the task, `0` means that no ID was assigned yet
+    #[serde(default)]
+    pub id: usize,
     pub task: String,
     pub state: TaskState,
+    #[serde(default)]
+    pub priority: Priority,
+    #[serde(default)]
+    pub due: Option<NaiveDate>,
+    #[serde(default)]
+    pub created_at: Option<DateTime<Utc>>,
+    /// When the task was last set as complete, cleared when it's no longer complete
+    #[serde(default)]
+    pub completed_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Steps of the task, their IDs come from the same counter as every other task
+    #[serde(default)]
+    pub subtasks: Vec<Task>,
+    /// IDs of the tasks that have to be finished before this one, they can be in any group
+    #[serde(default)]
+    pub blocked_by: Vec<usize>,
 }
 
 #[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
 pub enum TaskState {
     Complete,
     Incomplete,
+    InProgress,
+    Blocked { reason: Option<String> },
+    /// The task won't be done, it doesn't count towards progress
+    Cancelled,
+}
+
+/// The kinds of items that a group holds
+#[derive(Debug, PartialEq, Clone, Copy, ValueEnum)]
+pub enum ItemKind {
+    Task,
+    Note,
+}
+
+#[derive(Serialize, Deserialize, Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Default, ValueEnum)]
+pub enum Priority {
+    Low,
+    #[default]
+    Normal,
+    High,
+    Urgent,
 }
 
 impl Project {
     pub fn new() -> Self {
         Project {
+            schema_version: SCHEMA_VERSION,
             information: ProjectInformation::new(),
             groups: HashMap::new(),
             active_groups: vec![],
+            last_task_id: 0,
+            last_note_id: 0,
         }
     }
 
-    pub fn get_group(&self, name: &str) -> Group {
-        self.groups.get(name)
-            .expect("Specified group was not found")
-            .clone()
+    pub fn get_group(&self, name: &str) -> Result<Group, Error> {
+        self.groups
+            .get(name)
+            .cloned()
+            .ok_or_else(|| Error::Missing(format!("Specified group `{}` does not exist", name)))
     }
 
     pub fn get_group_descendants(&self, group_name: &str) -> Vec<String> {
-        let group = self.get_group(group_name);
-
         let mut children: Vec<String> = vec![];
+        let mut visited: HashSet<&str> = HashSet::from([group_name]);
+        self.collect_descendants(group_name, &mut visited, &mut children);
+
+        children
+    }
+
+    /// Walks the sub-groups depth first, skipping groups that were already visited so a
+    /// cycle in hand-edited data can't make it recurse forever
+    fn collect_descendants<'a>(
+        &'a self,
+        group_name: &str,
+        visited: &mut HashSet<&'a str>,
+        children: &mut Vec<String>,
+    ) {
+        let Some(group) = self.groups.get(group_name) else {
+            return;
+        };
+
         for child in &group.groups {
-            let g = self.get_group(child);
-            children.push(child.to_string());
-            children.append(&mut self.get_group_descendants(&g.name));
+            if self.groups.contains_key(child) && visited.insert(child) {
+                children.push(child.to_string());
+                self.collect_descendants(child, visited, children);
+            }
+        }
+    }
+
+    /// Finds the group that has the specified group as a child
+    pub fn get_group_parent(&self, group_name: &str) -> Option<String> {
+        self.groups
+            .values()
+            .find(|g| g.groups.iter().any(|child| child == group_name))
+            .map(|g| g.name.clone())
+    }
+
+    /// The names of the group's ancestors and the group itself, joined with `/`
+    pub fn get_group_path(&self, group_name: &str) -> String {
+        let mut path = vec![group_name.to_string()];
+
+        // a group can't have more ancestors than there are groups, unless parents loop around
+        while let Some(parent) = path.last().and_then(|g| self.get_group_parent(g)) {
+            if path.len() > self.groups.len() {
+                break;
+            }
+            path.push(parent);
         }
 
-        children
+        path.reverse();
+        path.join("/")
     }
 
-    pub fn clean(&mut self) {
-        let groups = self.groups.clone();
+    /// The closest group that is archived out of the group and its ancestors
+    pub fn archived_ancestor(&self, group_name: &str) -> Option<String> {
+        let mut current = group_name.to_string();
 
-        for k in groups.keys() {
-            let g = self.get_group(k);
-            for child in &g.groups {
-                if !self.groups.contains_key(child) {
-                    let index = g.groups.iter().position(|i| i == child).unwrap();
-                    let mut g = g.clone();
-                    g.groups.remove(index);
-                    println!("removing: {}", child);
-                    self.groups.insert(k.to_string(), g);
-                }
+        // the walk is bounded like `get_group_path`, in case parents loop around
+        for _ in 0..=self.groups.len() {
+            if self.groups.get(&current).is_some_and(|g| g.archived) {
+                return Some(current);
+            }
+            current = self.get_group_parent(&current)?;
+        }
+
+        None
+    }
+
+    /// Whether the group or one of its ancestors is archived
+    pub fn is_archived(&self, group_name: &str) -> bool {
+        self.archived_ancestor(group_name).is_some()
+    }
+
+    /// Renames a group and every reference to it, the new name must not be taken
+    pub fn rename_group(&mut self, old_name: &str, new_name: &str) {
+        let Some(mut group) = self.groups.remove(old_name) else {
+            return;
+        };
+        group.name = new_name.to_string();
+        self.groups.insert(new_name.to_string(), group);
+
+        for group in self.groups.values_mut() {
+            for child in group.groups.iter_mut().filter(|c| *c == old_name) {
+                *child = new_name.to_string();
+            }
+        }
+        for watched in self.active_groups.iter_mut().filter(|g| *g == old_name) {
+            *watched = new_name.to_string();
+        }
+    }
+
+    /// Moves a group under another parent, or to the top level if there's no parent
+    ///
+    /// The new parent must not be the group itself or one of its descendants.
+    pub fn reparent_group(&mut self, group_name: &str, new_parent: Option<&str>) {
+        if let Some(old_parent) = self.get_group_parent(group_name) {
+            if let Some(old_parent) = self.groups.get_mut(&old_parent) {
+                old_parent.groups.retain(|child| child != group_name);
             }
         }
+
+        if let Some(new_parent) = new_parent.and_then(|p| self.groups.get_mut(p)) {
+            new_parent.groups.push(group_name.to_string());
+        }
+    }
+
+    /// Reserves a new task ID
+    pub fn next_task_id(&mut self) -> usize {
+        self.last_task_id += 1;
+        self.last_task_id
+    }
+
+    /// Reserves a new note ID
+    pub fn next_note_id(&mut self) -> usize {
+        self.last_note_id += 1;
+        self.last_note_id
+    }
+
+    /// Removes sub-groups that don't exist from every group, returns the names that were removed
+    pub fn clean(&mut self) -> Vec<String> {
+        let mut removed: Vec<String> = vec![];
+        let names: HashSet<String> = self.groups.keys().cloned().collect();
+
+        for group in self.groups.values_mut() {
+            group.groups.retain(|child| {
+                let exists = names.contains(child);
+                if !exists {
+                    removed.push(child.to_string());
+                }
+                exists
+            });
+        }
+
+        removed
     }
 }
 
@@ -104,6 +284,13 @@ impl ProjectInformation {
     }
 }
 
+impl Default for ProjectInformation {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Group {
     pub fn new(name: &str) -> Self {
         Group {
@@ -111,23 +298,303 @@ impl Group {
             notes: vec![],
             tasks: vec![],
             groups: vec![],
+            archived: false,
+        }
+    }
+
+    /// Returns the index of the task with the specified ID, sub-tasks aren't searched
+    pub fn task_index(&self, id: usize) -> Option<usize> {
+        self.tasks.iter().position(|t| t.id == id)
+    }
+
+    /// Finds the task or sub-task with the specified ID
+    pub fn find_task(&self, id: usize) -> Option<&Task> {
+        self.all_tasks().into_iter().find(|t| t.id == id)
+    }
+
+    /// Finds the task or sub-task with the specified ID
+    pub fn find_task_mut(&mut self, id: usize) -> Option<&mut Task> {
+        fn find(tasks: &mut [Task], id: usize) -> Option<&mut Task> {
+            for task in tasks {
+                if task.id == id {
+                    return Some(task);
+                }
+                if let Some(task) = find(&mut task.subtasks, id) {
+                    return Some(task);
+                }
+            }
+            None
         }
+
+        find(&mut self.tasks, id)
+    }
+
+    /// Returns the ID of the task that the task with the specified ID is a sub-task of
+    pub fn parent_task(&self, id: usize) -> Option<usize> {
+        self.all_tasks()
+            .into_iter()
+            .find(|t| t.subtasks.iter().any(|s| s.id == id))
+            .map(|t| t.id)
+    }
+
+    /// Every task and sub-task of the group, each task comes right before its sub-tasks
+    pub fn all_tasks(&self) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = vec![];
+        for task in &self.tasks {
+            task.collect(&mut tasks);
+        }
+        tasks
+    }
+
+    /// Calls `f` on every task and sub-task of the group, in the order of `all_tasks`
+    pub fn for_each_task_mut(&mut self, f: &mut impl FnMut(&mut Task)) {
+        for task in &mut self.tasks {
+            task.for_each_mut(f);
+        }
+    }
+
+    /// Removes the tasks and sub-tasks with the specified IDs, along with their sub-tasks
+    pub fn remove_tasks(&mut self, ids: &[usize]) {
+        fn remove(tasks: &mut Vec<Task>, ids: &[usize]) {
+            tasks.retain(|t| !ids.contains(&t.id));
+            for task in tasks {
+                remove(&mut task.subtasks, ids);
+            }
+        }
+
+        remove(&mut self.tasks, ids);
+    }
+
+    /// Completes or reopens the parents of the changed tasks, so they match their sub-tasks
+    ///
+    /// A parent is completed once all of its sub-tasks are done, and a complete parent is reopened
+    /// when one of its sub-tasks is reopened. Parents are only completed if `can_complete` allows
+    /// it, and the changed tasks keep the state they were given.
+    /// Returns the IDs of the completed and the reopened parents.
+    pub fn update_parents(
+        &mut self,
+        changed: &[usize],
+        can_complete: impl Fn(&Task) -> bool,
+    ) -> (Vec<usize>, Vec<usize>) {
+        let mut completed: Vec<usize> = vec![];
+        let mut reopened: Vec<usize> = vec![];
+
+        for id in changed {
+            let mut current = *id;
+            while let Some(parent) = self.parent_task(current) {
+                if changed.contains(&parent) {
+                    break;
+                }
+
+                let closed = self.find_task(current).is_some_and(|t| t.state.is_closed());
+                let task = self.find_task_mut(parent).expect("Parents exist");
+                if closed {
+                    if task.state.is_closed() || !task.subtasks_finished() || !can_complete(task) {
+                        break;
+                    }
+                    task.set_state(TaskState::Complete);
+                    completed.push(parent);
+                } else {
+                    if task.state != TaskState::Complete {
+                        break;
+                    }
+                    task.set_state(TaskState::Incomplete);
+                    reopened.push(parent);
+                }
+                current = parent;
+            }
+        }
+
+        (completed, reopened)
+    }
+
+    /// Returns the index of the note with the specified ID
+    pub fn note_index(&self, id: usize) -> Option<usize> {
+        self.notes.iter().position(|n| n.id == id)
     }
 }
 
 impl Note {
-    pub fn new(note: &str) -> Self {
+    pub fn new(id: usize, note: &str) -> Self {
         Note {
+            id,
             note: note.to_string(),
+            created_at: Some(Utc::now()),
+            tags: vec![],
         }
     }
 }
 
 impl Task {
-    pub fn new(task: &str) -> Self {
+    pub fn new(id: usize, task: &str) -> Self {
         Task {
+            id,
             task: task.to_string(),
             state: TaskState::Incomplete,
+            priority: Priority::Normal,
+            due: None,
+            created_at: Some(Utc::now()),
+            completed_at: None,
+            tags: vec![],
+            subtasks: vec![],
+            blocked_by: vec![],
+        }
+    }
+
+    /// Changes the state of the task, keeping its completion time up to date
+    pub fn set_state(&mut self, state: TaskState) {
+        if state != TaskState::Complete {
+            self.completed_at = None;
+        } else if self.state != TaskState::Complete {
+            self.completed_at = Some(Utc::now());
+        }
+        self.state = state;
+    }
+
+    fn collect<'a>(&'a self, tasks: &mut Vec<&'a Task>) {
+        tasks.push(self);
+        for subtask in &self.subtasks {
+            subtask.collect(tasks);
+        }
+    }
+
+    /// Every sub-task of the task, including the sub-tasks of its sub-tasks
+    pub fn all_subtasks(&self) -> Vec<&Task> {
+        let mut tasks: Vec<&Task> = vec![];
+        for subtask in &self.subtasks {
+            subtask.collect(&mut tasks);
+        }
+        tasks
+    }
+
+    /// Calls `f` on the task and then on each of its sub-tasks
+    pub fn for_each_mut(&mut self, f: &mut impl FnMut(&mut Task)) {
+        f(self);
+        for subtask in &mut self.subtasks {
+            subtask.for_each_mut(f);
         }
     }
+
+    /// Completes the task along with every sub-task that isn't cancelled
+    pub fn complete_all(&mut self) {
+        self.for_each_mut(&mut |task| {
+            if task.state != TaskState::Cancelled {
+                task.set_state(TaskState::Complete);
+            }
+        });
+    }
+
+    /// Whether every sub-task is done, which means complete or cancelled with at least one complete
+    pub fn subtasks_finished(&self) -> bool {
+        !self.subtasks.is_empty()
+            && self.subtasks.iter().all(|t| t.state.is_closed())
+            && self.subtasks.iter().any(|t| t.state == TaskState::Complete)
+    }
+
+    /// How much of the task is done, from 0 to 1
+    ///
+    /// A task with sub-tasks is as done as its sub-tasks are on average, leaving out cancelled
+    /// ones.
+    pub fn progress(&self) -> f64 {
+        if self.state == TaskState::Complete {
+            return 1.0;
+        }
+
+        let counted: Vec<&Task> = self
+            .subtasks
+            .iter()
+            .filter(|t| t.state != TaskState::Cancelled)
+            .collect();
+        if counted.is_empty() {
+            return 0.0;
+        }
+        counted.iter().map(|t| t.progress()).sum::<f64>() / counted.len() as f64
+    }
+
+    /// How many of the sub-tasks are complete, and how many count, which leaves out cancelled ones
+    pub fn subtask_counts(&self) -> (usize, usize) {
+        let counted = self
+            .subtasks
+            .iter()
+            .filter(|t| t.state != TaskState::Cancelled);
+        let complete = counted
+            .clone()
+            .filter(|t| t.state == TaskState::Complete)
+            .count();
+        (complete, counted.count())
+    }
+
+    /// Whether the task is unfinished and its due date has passed
+    pub fn is_overdue(&self, today: NaiveDate) -> bool {
+        !self.state.is_closed() && self.due.is_some_and(|due| due < today)
+    }
+}
+
+impl TaskState {
+    /// Whether nothing is left to do for the task, either it's complete or cancelled
+    pub fn is_closed(&self) -> bool {
+        matches!(self, TaskState::Complete | TaskState::Cancelled)
+    }
+
+    /// The character shown between the brackets of a task's checkbox
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            TaskState::Complete => "x",
+            TaskState::Incomplete => " ",
+            TaskState::InProgress => "~",
+            TaskState::Blocked { .. } => "!",
+            TaskState::Cancelled => "-",
+        }
+    }
+}
+
+impl ItemKind {
+    /// Returns the tags of an item in a group, `None` if there's no item with the ID
+    pub fn tags_mut(self, group: &mut Group, id: usize) -> Option<&mut Vec<String>> {
+        match self {
+            ItemKind::Task => group.find_task_mut(id).map(|t| &mut t.tags),
+            ItemKind::Note => group.notes.iter_mut().find(|n| n.id == id).map(|n| &mut n.tags),
+        }
+    }
+
+    /// Returns the text of an item in a group, `None` if there's no item with the ID
+    pub fn text_mut(self, group: &mut Group, id: usize) -> Option<&mut String> {
+        match self {
+            ItemKind::Task => group.find_task_mut(id).map(|t| &mut t.task),
+            ItemKind::Note => group.notes.iter_mut().find(|n| n.id == id).map(|n| &mut n.note),
+        }
+    }
+}
+
+impl fmt::Display for ItemKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ItemKind::Task => write!(f, "task"),
+            ItemKind::Note => write!(f, "note"),
+        }
+    }
+}
+
+impl Priority {
+    /// How much a task with this priority counts towards weighted progress
+    pub fn weight(&self) -> usize {
+        match self {
+            Priority::Low => 1,
+            Priority::Normal => 2,
+            Priority::High => 3,
+            Priority::Urgent => 4,
+        }
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            Priority::Low => "low",
+            Priority::Normal => "normal",
+            Priority::High => "high",
+            Priority::Urgent => "urgent",
+        };
+        write!(f, "{}", name)
+    }
 }
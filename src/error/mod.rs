@@ -0,0 +1,149 @@
+use clap::error::ErrorKind;
+use std::{fmt, io, path::PathBuf};
+
+/// Exit code for commands that can't be done with the current state of the project
+pub const EXIT_INVALID: i32 = 1;
+/// Exit code for invalid or missing arguments
+pub const EXIT_USAGE: i32 = 2;
+/// Exit code for when no project was found
+pub const EXIT_NO_PROJECT: i32 = 3;
+/// Exit code for when a specified group or item doesn't exist
+pub const EXIT_MISSING: i32 = 4;
+/// Exit code for failures while reading or writing the project file
+pub const EXIT_IO: i32 = 5;
+/// Exit code for project files that can't be understood
+pub const EXIT_DATA: i32 = 6;
+/// Exit code for when another pmgr process holds the project lock
+pub const EXIT_LOCKED: i32 = 7;
+
+/// Errors that can happen while running pmgr
+#[derive(Debug)]
+pub enum Error {
+    /// No project file was found in the current or any parent directory
+    NotFound,
+    /// The project file is not valid project data
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// Reading or writing the project file failed
+    Io(io::Error),
+    /// The project file was written by a newer, incompatible version of pmgr
+    VersionMismatch { found: u32, supported: u32 },
+    /// The project data has broken links between groups, `pmgr doctor` can repair it
+    Integrity { problems: usize },
+    /// Another process held the project lock for too long
+    Locked { pid: Option<u32>, path: PathBuf },
+    /// The given arguments are invalid or missing
+    Usage(String),
+    /// A specified group, task or note doesn't exist
+    Missing(String),
+    /// The command can't be done with the current state of the project
+    Invalid(String),
+}
+
+impl Error {
+    /// The exit code that the process should end with
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Error::NotFound => EXIT_NO_PROJECT,
+            Error::Parse { .. } | Error::VersionMismatch { .. } | Error::Integrity { .. } => {
+                EXIT_DATA
+            }
+            Error::Io(_) => EXIT_IO,
+            Error::Locked { .. } => EXIT_LOCKED,
+            Error::Usage(_) => EXIT_USAGE,
+            Error::Missing(_) => EXIT_MISSING,
+            Error::Invalid(_) => EXIT_INVALID,
+        }
+    }
+
+    /// The kind of clap error used when printing this error
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Error::Usage(_) => ErrorKind::MissingRequiredArgument,
+            Error::Missing(_) => ErrorKind::InvalidValue,
+            Error::Invalid(_) => ErrorKind::ValueValidation,
+            _ => ErrorKind::Io,
+        }
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::NotFound => write!(f, "Failed to get project root: No project root found"),
+            Error::Parse { line: 0, message, .. } => {
+                write!(f, "Failed to parse project data: {}", message)
+            }
+            Error::Parse {
+                line,
+                column,
+                message,
+            } => write!(
+                f,
+                "Failed to parse project data at line {}, column {}: {}",
+                line, column, message
+            ),
+            Error::Io(e) => write!(f, "{}", e),
+            Error::VersionMismatch { found, supported } => write!(
+                f,
+                "Project data has schema version {}, but this version of pmgr supports up to {}",
+                found, supported
+            ),
+            Error::Integrity { problems } => write!(
+                f,
+                "Project data has {} integrity problem(s), run `pmgr doctor` to see them or `pmgr doctor --fix` to repair them",
+                problems
+            ),
+            Error::Locked { pid, path } => {
+                match pid {
+                    Some(pid) => write!(f, "Project is locked by PID {}", pid)?,
+                    None => write!(f, "Project is locked by another process")?,
+                }
+                write!(
+                    f,
+                    " (remove {:?} if that process is no longer running)",
+                    path
+                )
+            }
+            Error::Usage(message) | Error::Missing(message) | Error::Invalid(message) => {
+                write!(f, "{}", message)
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for Error {
+    fn from(e: serde_json::Error) -> Self {
+        if e.is_io() {
+            return Error::Io(e.into());
+        }
+
+        // serde_json appends the position to its message, it's shown separately
+        let position = format!(" at line {} column {}", e.line(), e.column());
+        let message = e.to_string();
+
+        Error::Parse {
+            line: e.line(),
+            column: e.column(),
+            message: message.trim_end_matches(&position).to_string(),
+        }
+    }
+}
@@ -1,6 +1,14 @@
 pub mod utils;
 pub mod commands;
 pub mod data;
+pub mod error;
+pub mod store;
+pub mod query;
+pub mod output;
 
 pub use utils::*;
 pub use commands::*;
+pub use error::Error;
+pub use store::Store;
+pub use query::Query;
+pub use output::Format;
@@ -1,5 +1,6 @@
-use clap::Parser;
-use pmgr::commands::{Cli, Commands, Command};
+use clap::{CommandFactory, Parser};
+use pmgr::commands::{Cli, Commands, Command, Report};
+use std::process;
 
 #[cfg(debug_assertions)]
 const FILE_NAME: &str = ".debug.pmgr.json";
@@ -9,11 +10,11 @@ const FILE_NAME: &str = ".pmgr.json";
 fn main() {
     let cli = Cli::parse();
 
-    match cli.command {
+    let result = match cli.command {
         Commands::Check(args) => args.run(FILE_NAME),
-        Commands::Read(args) => args.run(FILE_NAME),
-        Commands::List(args) => args.run(FILE_NAME),
-        Commands::Info(args) => args.run(FILE_NAME),
+        Commands::Read(args) => args.report(FILE_NAME, cli.format),
+        Commands::List(args) => args.report(FILE_NAME, cli.format),
+        Commands::Info(args) => args.report(FILE_NAME, cli.format),
         Commands::Init(args) => args.run(FILE_NAME),
         Commands::Set(args) => {
             match args.set_commands {
@@ -25,6 +26,10 @@ fn main() {
         Commands::ShellCompletions(args) => args.run(),
         Commands::Create(args) => args.run(FILE_NAME),
         Commands::Delete(args) => args.run(FILE_NAME),
+        Commands::Rename(args) => args.run(FILE_NAME),
+        Commands::Reparent(args) => args.run(FILE_NAME),
+        Commands::Archive(args) => args.run(FILE_NAME),
+        Commands::Unarchive(args) => args.run(FILE_NAME),
         Commands::Watch(args) => args.run(FILE_NAME),
         Commands::Unwatch(args) => args.run(FILE_NAME),
         Commands::Add(args) => {
@@ -39,12 +44,59 @@ fn main() {
                 pmgr::remove::RemoveCommands::Task(args) => args.run(FILE_NAME),
             }
         },
+        Commands::Edit(args) => {
+            match args.edit_commands {
+                pmgr::edit::EditCommands::Note(args) => args.run(FILE_NAME),
+                pmgr::edit::EditCommands::Task(args) => args.run(FILE_NAME),
+            }
+        },
+        Commands::Move(args) => {
+            match args.move_commands {
+                pmgr::transfer::MoveCommands::Note(args) => args.run(FILE_NAME),
+                pmgr::transfer::MoveCommands::Task(args) => args.run(FILE_NAME),
+            }
+        },
+        Commands::Copy(args) => {
+            match args.copy_commands {
+                pmgr::transfer::CopyCommands::Note(args) => args.run(FILE_NAME),
+                pmgr::transfer::CopyCommands::Task(args) => args.run(FILE_NAME),
+            }
+        },
         Commands::Task(args) => {
             match args.task_commands {
                 pmgr::task::TaskCommands::Complete(args) => args.run(FILE_NAME),
                 pmgr::task::TaskCommands::Undo(args) => args.run(FILE_NAME),
-                pmgr::task::TaskCommands::Progress(args) => args.run(FILE_NAME),
+                pmgr::task::TaskCommands::Start(args) => args.run(FILE_NAME),
+                pmgr::task::TaskCommands::Block(args) => args.run(FILE_NAME),
+                pmgr::task::TaskCommands::Cancel(args) => args.run(FILE_NAME),
+                pmgr::task::TaskCommands::Priority(args) => args.run(FILE_NAME),
+                pmgr::task::TaskCommands::Due(args) => args.run(FILE_NAME),
+                pmgr::task::TaskCommands::Progress(args) => args.report(FILE_NAME, cli.format),
+                pmgr::task::TaskCommands::Reorder(args) => args.run(FILE_NAME),
+                pmgr::task::TaskCommands::Sort(args) => args.run(FILE_NAME),
+                pmgr::task::TaskCommands::Link(args) => args.run(FILE_NAME),
+                pmgr::task::TaskCommands::Unlink(args) => args.run(FILE_NAME),
+                pmgr::task::TaskCommands::Deps(args) => args.report(FILE_NAME, cli.format),
+            }
+        },
+        Commands::Tag(args) => {
+            match args.tag_commands {
+                pmgr::tag::TagCommands::Add(args) => args.run(FILE_NAME),
+                pmgr::tag::TagCommands::Remove(args) => args.run(FILE_NAME),
             }
         },
+        Commands::Tags(args) => args.report(FILE_NAME, cli.format),
+        Commands::Search(args) => args.report(FILE_NAME, cli.format),
+        Commands::Agenda(args) => args.report(FILE_NAME, cli.format),
+        Commands::Undo(args) => args.run(FILE_NAME),
+        Commands::Redo(args) => args.run(FILE_NAME),
+        Commands::History(args) => args.report(FILE_NAME, cli.format),
+        Commands::Doctor(args) => args.run(FILE_NAME),
+        Commands::Migrate(args) => args.run(FILE_NAME),
+    };
+
+    if let Err(e) = result {
+        let _ = Cli::command().error(e.kind(), &e).print();
+        process::exit(e.exit_code());
     }
 }
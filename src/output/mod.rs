@@ -0,0 +1,232 @@
+use crate::data::{Note, Priority, Task, TaskState};
+use crate::Error;
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::ValueEnum;
+use serde::Serialize;
+use std::io::{self, Write};
+
+/// How read commands print their results
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum Format {
+    /// Coloured text for people to read
+    #[default]
+    Text,
+    Json,
+    Yaml,
+    Csv,
+    Tsv,
+}
+
+impl Format {
+    pub fn is_text(self) -> bool {
+        self == Format::Text
+    }
+}
+
+/// A task as it appears in structured output
+#[derive(Serialize, Debug, PartialEq)]
+pub struct TaskView {
+    pub group: String,
+    pub id: usize,
+    pub text: String,
+    pub state: &'static str,
+    pub blocked_reason: Option<String>,
+    pub priority: &'static str,
+    pub due: Option<NaiveDate>,
+    pub overdue: bool,
+    pub created_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+    /// The ID of the task that this one is a sub-task of
+    pub parent: Option<usize>,
+    pub subtasks: Vec<TaskView>,
+    /// IDs of the tasks that this one waits on
+    pub blocked_by: Vec<usize>,
+}
+
+/// A note as it appears in structured output
+#[derive(Serialize, Debug, PartialEq)]
+pub struct NoteView {
+    pub group: String,
+    pub id: usize,
+    pub text: String,
+    pub created_at: Option<DateTime<Utc>>,
+    pub tags: Vec<String>,
+}
+
+/// A task or a note as a single row of a table, used for CSV and TSV output
+#[derive(Serialize, Debug, PartialEq)]
+pub struct ItemRow {
+    pub kind: &'static str,
+    pub group: String,
+    pub id: usize,
+    pub text: String,
+    pub state: Option<&'static str>,
+    pub blocked_reason: Option<String>,
+    pub priority: Option<&'static str>,
+    pub due: Option<NaiveDate>,
+    pub overdue: Option<bool>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub completed_at: Option<DateTime<Utc>>,
+    /// Tags separated by spaces, as tags can't contain whitespace
+    pub tags: String,
+    pub parent: Option<usize>,
+    /// IDs separated by spaces
+    pub blocked_by: String,
+}
+
+pub fn state_name(state: &TaskState) -> &'static str {
+    match state {
+        TaskState::Incomplete => "incomplete",
+        TaskState::InProgress => "in-progress",
+        TaskState::Blocked { .. } => "blocked",
+        TaskState::Complete => "complete",
+        TaskState::Cancelled => "cancelled",
+    }
+}
+
+pub fn priority_name(priority: Priority) -> &'static str {
+    match priority {
+        Priority::Low => "low",
+        Priority::Normal => "normal",
+        Priority::High => "high",
+        Priority::Urgent => "urgent",
+    }
+}
+
+impl TaskView {
+    /// Views the task along with its sub-tasks, the task itself is viewed as having no parent
+    pub fn new(group: &str, task: &Task, today: NaiveDate) -> Self {
+        let blocked_reason = match &task.state {
+            TaskState::Blocked { reason } => reason.clone(),
+            _ => None,
+        };
+
+        TaskView {
+            group: group.to_string(),
+            id: task.id,
+            text: task.task.clone(),
+            state: state_name(&task.state),
+            blocked_reason,
+            priority: priority_name(task.priority),
+            due: task.due,
+            overdue: task.is_overdue(today),
+            created_at: task.created_at,
+            completed_at: task.completed_at,
+            tags: task.tags.clone(),
+            parent: None,
+            subtasks: task
+                .subtasks
+                .iter()
+                .map(|t| TaskView {
+                    parent: Some(task.id),
+                    ..TaskView::new(group, t, today)
+                })
+                .collect(),
+            blocked_by: task.blocked_by.clone(),
+        }
+    }
+}
+
+impl NoteView {
+    pub fn new(group: &str, note: &Note) -> Self {
+        NoteView {
+            group: group.to_string(),
+            id: note.id,
+            text: note.note.clone(),
+            created_at: note.created_at,
+            tags: note.tags.clone(),
+        }
+    }
+}
+
+impl From<&TaskView> for ItemRow {
+    fn from(task: &TaskView) -> Self {
+        ItemRow {
+            kind: "task",
+            group: task.group.clone(),
+            id: task.id,
+            text: task.text.clone(),
+            state: Some(task.state),
+            blocked_reason: task.blocked_reason.clone(),
+            priority: Some(task.priority),
+            due: task.due,
+            overdue: Some(task.overdue),
+            created_at: task.created_at,
+            completed_at: task.completed_at,
+            tags: task.tags.join(" "),
+            parent: task.parent,
+            blocked_by: task
+                .blocked_by
+                .iter()
+                .map(|id| id.to_string())
+                .collect::<Vec<String>>()
+                .join(" "),
+        }
+    }
+}
+
+impl ItemRow {
+    /// Rows for a task and all of its sub-tasks, each task comes right before its sub-tasks
+    pub fn task_rows(task: &TaskView) -> Vec<ItemRow> {
+        let mut rows = vec![ItemRow::from(task)];
+        for subtask in &task.subtasks {
+            rows.extend(ItemRow::task_rows(subtask));
+        }
+        rows
+    }
+}
+
+impl From<&NoteView> for ItemRow {
+    fn from(note: &NoteView) -> Self {
+        ItemRow {
+            kind: "note",
+            group: note.group.clone(),
+            id: note.id,
+            text: note.text.clone(),
+            state: None,
+            blocked_reason: None,
+            priority: None,
+            due: None,
+            overdue: None,
+            created_at: note.created_at,
+            completed_at: None,
+            tags: note.tags.join(" "),
+            parent: None,
+            blocked_by: String::new(),
+        }
+    }
+}
+
+/// Prints `document` as JSON or YAML, or `rows` as CSV or TSV
+///
+/// Tables can't hold nested data, so commands give their results in both shapes.
+pub fn print<D: Serialize, R: Serialize>(
+    format: Format,
+    document: &D,
+    rows: &[R],
+) -> Result<(), Error> {
+    let mut stdout = io::stdout().lock();
+
+    match format {
+        Format::Text | Format::Json => {
+            serde_json::to_writer_pretty(&mut stdout, document).map_err(io::Error::from)?;
+            writeln!(stdout)?;
+        }
+        Format::Yaml => {
+            serde_yaml::to_writer(&mut stdout, document).map_err(io::Error::other)?;
+        }
+        Format::Csv | Format::Tsv => {
+            let delimiter = if format == Format::Csv { b',' } else { b'\t' };
+            let mut writer = csv::WriterBuilder::new()
+                .delimiter(delimiter)
+                .from_writer(stdout);
+            for row in rows {
+                writer.serialize(row).map_err(io::Error::from)?;
+            }
+            writer.flush()?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,209 @@
+use crate::data::{Note, Priority, Project, Task, TaskState};
+use crate::{utils, Error};
+use chrono::{DateTime, NaiveDate, Utc};
+use clap::{Args, ValueEnum};
+
+/// Filters over tasks and notes, shared by every command that selects items
+///
+/// Every given filter has to match for an item to be selected. Filters that only make sense
+/// for tasks (state, priority, due and completion dates) leave out notes when they're used.
+#[derive(Args, Clone, Default, Debug)]
+pub struct Query {
+    /// Only include tasks in this state, can be used multiple times
+    #[arg(short, long = "state", value_enum)]
+    pub states: Vec<StateFilter>,
+    /// Only include tasks and notes with this tag, can be used multiple times
+    #[arg(short, long = "tag", value_parser = utils::parse_tag)]
+    pub tags: Vec<String>,
+    /// Only include tasks and notes whose text contains this (case insensitive)
+    #[arg(long)]
+    pub text: Option<String>,
+    /// Only include tasks with this priority or higher
+    #[arg(long, value_enum)]
+    pub min_priority: Option<Priority>,
+    /// Only include tasks that are due before this date
+    #[arg(long, value_parser = utils::parse_date_arg, allow_hyphen_values = true)]
+    pub due_before: Option<NaiveDate>,
+    /// Only include unfinished tasks whose due date has passed
+    #[arg(long)]
+    pub overdue: bool,
+    /// Only include tasks that were completed on or after this date
+    #[arg(long, value_parser = utils::parse_date_arg, allow_hyphen_values = true)]
+    pub completed_since: Option<NaiveDate>,
+    /// Only include tasks and notes that were created before this date
+    #[arg(long, value_parser = utils::parse_date_arg, allow_hyphen_values = true)]
+    pub created_before: Option<NaiveDate>,
+    /// Also include archived groups and their sub-groups
+    #[arg(long)]
+    pub include_archived: bool,
+}
+
+/// Task states that can be queried, `open` and `closed` cover several states at once
+#[derive(ValueEnum, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum StateFilter {
+    Incomplete,
+    InProgress,
+    Blocked,
+    Complete,
+    Cancelled,
+    /// Any state with something left to do
+    Open,
+    /// Complete or cancelled
+    Closed,
+}
+
+impl StateFilter {
+    pub fn matches(self, state: &TaskState) -> bool {
+        match self {
+            StateFilter::Incomplete => *state == TaskState::Incomplete,
+            StateFilter::InProgress => *state == TaskState::InProgress,
+            StateFilter::Blocked => matches!(state, TaskState::Blocked { .. }),
+            StateFilter::Complete => *state == TaskState::Complete,
+            StateFilter::Cancelled => *state == TaskState::Cancelled,
+            StateFilter::Open => !state.is_closed(),
+            StateFilter::Closed => state.is_closed(),
+        }
+    }
+}
+
+impl Query {
+    /// Whether any filter is given
+    pub fn is_active(&self) -> bool {
+        !self.tags.is_empty()
+            || self.text.is_some()
+            || self.created_before.is_some()
+            || self.tasks_only()
+    }
+
+    /// Whether a filter that only applies to tasks is given
+    pub fn tasks_only(&self) -> bool {
+        !self.states.is_empty()
+            || self.min_priority.is_some()
+            || self.due_before.is_some()
+            || self.overdue
+            || self.completed_since.is_some()
+    }
+
+    pub fn matches_task(&self, task: &Task) -> bool {
+        let state = self.states.is_empty() || self.states.iter().any(|s| s.matches(&task.state));
+        let priority = self.min_priority.is_none_or(|min| task.priority >= min);
+        let due_before = self
+            .due_before
+            .is_none_or(|before| task.due.is_some_and(|due| due < before));
+        let overdue = !self.overdue || task.is_overdue(utils::today());
+        let completed_since = self.completed_since.is_none_or(|since| {
+            task.completed_at
+                .is_some_and(|completed_at| utils::local_date(completed_at) >= since)
+        });
+
+        state
+            && priority
+            && due_before
+            && overdue
+            && completed_since
+            && self.matches_common(&task.task, &task.tags, task.created_at)
+    }
+
+    /// Keeps the sub-tasks that match, and the task itself if it or any of its sub-tasks do
+    ///
+    /// Parents of matching sub-tasks are kept even when they don't match, so the nesting shows.
+    pub fn prune(&self, task: &Task) -> Option<Task> {
+        let subtasks: Vec<Task> = task.subtasks.iter().filter_map(|t| self.prune(t)).collect();
+        if subtasks.is_empty() && !self.matches_task(task) {
+            return None;
+        }
+
+        Some(Task {
+            subtasks,
+            ..task.clone()
+        })
+    }
+
+    pub fn matches_note(&self, note: &Note) -> bool {
+        !self.tasks_only() && self.matches_common(&note.note, &note.tags, note.created_at)
+    }
+
+    /// Filters that apply to both tasks and notes
+    fn matches_common(
+        &self,
+        text: &str,
+        tags: &[String],
+        created_at: Option<DateTime<Utc>>,
+    ) -> bool {
+        let text = self
+            .text
+            .as_ref()
+            .is_none_or(|query| text.to_lowercase().contains(&query.to_lowercase()));
+        let tags = self.tags.iter().all(|tag| tags.contains(tag));
+        let created_before = self.created_before.is_none_or(|before| {
+            created_at.is_some_and(|created_at| utils::local_date(created_at) < before)
+        });
+
+        text && tags && created_before
+    }
+}
+
+/// Picks the groups a command works on
+///
+/// A group name selects the group and its descendants, `all` selects every group. Otherwise the
+/// watched groups are used, unless a query is given, which searches every group instead.
+///
+/// Archived groups are left out unless the query includes them, or the named group is archived
+/// itself, as asking for it by name is asking for its archived contents.
+pub fn select_groups(
+    data: &Project,
+    group_name: Option<&str>,
+    all: bool,
+    query: &Query,
+) -> Result<Vec<String>, Error> {
+    let hidden = |group: &String| !query.include_archived && data.is_archived(group);
+
+    if let (Some(group_name), false) = (group_name, all) {
+        if !data.groups.contains_key(group_name) {
+            return Err(Error::Missing(format!(
+                "Specified group `{}` does not exist",
+                group_name
+            )));
+        }
+
+        let mut groups = vec![group_name.to_string()];
+        groups.append(&mut data.get_group_descendants(group_name));
+        if !data.is_archived(group_name) {
+            groups.retain(|g| !hidden(g));
+        }
+        return Ok(groups);
+    }
+
+    if all || query.is_active() {
+        if data.groups.is_empty() {
+            return Err(Error::Invalid("No groups exist in the project".to_string()));
+        }
+
+        let mut groups: Vec<String> = data.groups.keys().cloned().collect();
+        groups.retain(|g| !hidden(g));
+        if groups.is_empty() {
+            return Err(Error::Invalid(
+                "All groups are archived (Use --include-archived flag to include them)"
+                    .to_string(),
+            ));
+        }
+        groups.sort();
+        return Ok(groups);
+    }
+
+    if data.active_groups.is_empty() {
+        return Err(Error::Invalid(
+            "No groups are being watched (Use --all flag to use all groups)".to_string(),
+        ));
+    }
+
+    let mut groups = data.active_groups.clone();
+    groups.retain(|g| !hidden(g));
+    if groups.is_empty() {
+        return Err(Error::Invalid(
+            "All watched groups are archived (Use --include-archived flag to include them)"
+                .to_string(),
+        ));
+    }
+    Ok(groups)
+}
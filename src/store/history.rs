@@ -0,0 +1,73 @@
+use crate::data::Project;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// How many changes are kept in the history, older ones are dropped
+pub const HISTORY_LIMIT: usize = 50;
+
+/// Snapshots of the project around each change, used to undo and redo them
+///
+/// Only one side of each change is kept, the other side is the next change's snapshot or `head`.
+#[derive(Serialize, Deserialize, Default, Debug)]
+pub struct History {
+    pub entries: Vec<Entry>,
+    /// How many of the entries are applied, the ones after it were undone and can be redone
+    pub position: usize,
+    /// The project as the last recorded, undone or redone change left it
+    #[serde(default)]
+    pub head: Option<Project>,
+}
+
+/// A change made by a command
+#[derive(Serialize, Deserialize, Debug)]
+pub struct Entry {
+    pub summary: String,
+    pub at: DateTime<Utc>,
+    /// The project before the change while it's applied, and after it once it's undone
+    pub snapshot: Project,
+}
+
+impl History {
+    /// Adds a change, the undone changes can't be redone anymore
+    pub fn record(&mut self, summary: String, before: Project, after: Project) {
+        self.entries.truncate(self.position);
+        self.entries.push(Entry {
+            summary,
+            at: Utc::now(),
+            snapshot: before,
+        });
+
+        if self.entries.len() > HISTORY_LIMIT {
+            let excess = self.entries.len() - HISTORY_LIMIT;
+            self.entries.drain(..excess);
+        }
+        self.position = self.entries.len();
+        self.head = Some(after);
+    }
+
+    /// Undoes the last applied change or redoes the first undone one, after the project was set
+    /// to `restored` from its snapshot
+    ///
+    /// The change keeps the previous head instead, so the step can be reversed.
+    pub fn step(&mut self, undo: bool, restored: Project) {
+        let index = if undo {
+            self.position - 1
+        } else {
+            self.position
+        };
+        if let Some(previous) = self.head.replace(restored) {
+            self.entries[index].snapshot = previous;
+        }
+        self.position = if undo { index } else { index + 1 };
+    }
+
+    /// The change that `undo` would revert
+    pub fn last_applied(&self) -> Option<&Entry> {
+        self.position.checked_sub(1).map(|i| &self.entries[i])
+    }
+
+    /// The change that `redo` would apply again
+    pub fn next_undone(&self) -> Option<&Entry> {
+        self.entries.get(self.position)
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{data::SCHEMA_VERSION, Error};
+use serde_json::Value;
+
+/// A step that upgrades project data from one schema version to the next
+struct Migration {
+    from: u32,
+    description: &'static str,
+    apply: fn(&mut Value) -> Vec<String>,
+}
+
+/// A migration that was applied to project data
+pub struct Applied {
+    pub from: u32,
+    pub to: u32,
+    pub description: &'static str,
+    /// A line for each change that was made to the data
+    pub changes: Vec<String>,
+}
+
+/// Every migration in order, each one upgrades data from version `from` to `from + 1`
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        from: 0,
+        description: "Give tasks and notes persistent IDs",
+        apply: assign_ids,
+    },
+    Migration {
+        from: 1,
+        description: "Add task states, priorities, due dates, timestamps, tags, archived groups, \
+                      sub-tasks and dependencies",
+        apply: add_task_details,
+    },
+];
+
+/// Returns the schema version of raw project data, files without one are version 0
+pub fn schema_version(project: &Value) -> u32 {
+    project
+        .get("schema_version")
+        .and_then(Value::as_u64)
+        .map_or(0, |v| v as u32)
+}
+
+/// Upgrades raw project data to the current schema version
+pub fn migrate(project: &mut Value) -> Result<Vec<Applied>, Error> {
+    // anything that isn't an object is left for deserialization to report
+    if !project.is_object() {
+        return Ok(vec![]);
+    }
+
+    let found = schema_version(project);
+    if found > SCHEMA_VERSION {
+        return Err(Error::VersionMismatch {
+            found,
+            supported: SCHEMA_VERSION,
+        });
+    }
+
+    let mut applied = vec![];
+    for migration in MIGRATIONS.iter().filter(|m| m.from >= found) {
+        let changes = (migration.apply)(project);
+        applied.push(Applied {
+            from: migration.from,
+            to: migration.from + 1,
+            description: migration.description,
+            changes,
+        });
+    }
+
+    project["schema_version"] = SCHEMA_VERSION.into();
+
+    Ok(applied)
+}
+
+fn sorted_group_names(project: &Value) -> Vec<String> {
+    let mut names: Vec<String> = project
+        .get("groups")
+        .and_then(Value::as_object)
+        .map(|groups| groups.keys().cloned().collect())
+        .unwrap_or_default();
+    names.sort();
+    names
+}
+
+fn group_items<'a>(project: &'a mut Value, group: &str, items: &str) -> Option<&'a mut Vec<Value>> {
+    project
+        .get_mut("groups")?
+        .get_mut(group)?
+        .get_mut(items)?
+        .as_array_mut()
+}
+
+fn item_id(item: &Value) -> u64 {
+    item.get("id").and_then(Value::as_u64).unwrap_or(0)
+}
+
+fn assign_ids(project: &mut Value) -> Vec<String> {
+    let mut changes = vec![];
+    let names = sorted_group_names(project);
+
+    for (items, text, kind, counter) in [
+        ("tasks", "task", "Task", "last_task_id"),
+        ("notes", "note", "Note", "last_note_id"),
+    ] {
+        let mut last_id = project.get(counter).and_then(Value::as_u64).unwrap_or(0);
+
+        for name in &names {
+            for item in group_items(project, name, items).into_iter().flatten() {
+                last_id = last_id.max(item_id(item));
+            }
+        }
+
+        for name in &names {
+            for item in group_items(project, name, items).into_iter().flatten() {
+                if item_id(item) != 0 {
+                    continue;
+                }
+                let Some(item) = item.as_object_mut() else {
+                    continue;
+                };
+                last_id += 1;
+                item.insert("id".to_string(), last_id.into());
+                changes.push(format!(
+                    "{} `{}` in group `{}` gets ID {}",
+                    kind,
+                    item.get(text).and_then(Value::as_str).unwrap_or_default(),
+                    name,
+                    last_id
+                ));
+            }
+        }
+
+        if let Some(project) = project.as_object_mut() {
+            project.insert(counter.to_string(), last_id.into());
+        }
+    }
+
+    changes
+}
+
+// every new field has a default, the version only keeps older versions of pmgr from
+// loading the data and silently dropping them on save
+fn add_task_details(_project: &mut Value) -> Vec<String> {
+    vec![]
+}
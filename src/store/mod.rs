@@ -0,0 +1,203 @@
+pub mod history;
+pub mod migrate;
+
+use crate::{data::Project, utils, Error};
+use history::History;
+use migrate::Applied;
+use std::{
+    ffi::OsString,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
+    process, thread,
+    time::{Duration, Instant},
+};
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// Handle to a project file on disk
+pub struct Store {
+    path: PathBuf,
+}
+
+/// Advisory lock on a project file, released when dropped
+pub struct Lock {
+    path: PathBuf,
+}
+
+impl Drop for Lock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+impl Store {
+    /// Creates a store for the project file at the specified path
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Store { path: path.into() }
+    }
+
+    /// Looks for the project file in the current and parent directories
+    pub fn find(file_name: &str) -> Result<Self> {
+        match utils::check_data(file_name) {
+            Ok(path) => Ok(Store::new(path)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Err(Error::NotFound),
+            Err(e) => Err(Error::Io(e)),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Path of the copy of the project file from before the last write
+    pub fn backup_path(&self) -> PathBuf {
+        self.with_suffix(".bak")
+    }
+
+    /// Path of the file that keeps the history of changes, used by undo and redo
+    pub fn history_path(&self) -> PathBuf {
+        self.with_suffix(".history")
+    }
+
+    /// Path of the file that marks the project as locked
+    pub fn lock_path(&self) -> PathBuf {
+        self.with_suffix(".lock")
+    }
+
+    /// Takes the project lock, waiting up to `timeout` for other processes to release it
+    pub fn lock(&self, timeout: Duration) -> Result<Lock> {
+        let path = self.lock_path();
+        let start = Instant::now();
+
+        loop {
+            match OpenOptions::new().write(true).create_new(true).open(&path) {
+                Ok(mut file) => {
+                    let lock = Lock { path };
+                    write!(file, "{}", process::id())?;
+                    return Ok(lock);
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    if start.elapsed() >= timeout {
+                        let pid = fs::read_to_string(&path)
+                            .ok()
+                            .and_then(|pid| pid.trim().parse().ok());
+                        return Err(Error::Locked { pid, path });
+                    }
+                    thread::sleep(Duration::from_millis(10));
+                }
+                Err(e) => return Err(Error::Io(e)),
+            }
+        }
+    }
+
+    fn with_suffix(&self, suffix: &str) -> PathBuf {
+        let mut path = OsString::from(self.path.as_os_str());
+        path.push(suffix);
+        PathBuf::from(path)
+    }
+
+    /// Reads the project data, upgrading it to the current schema version
+    pub fn load(&self) -> Result<Project> {
+        let data = self.load_unchecked()?;
+
+        // broken links between groups would crash commands that walk the group tree
+        let problems = data.check_groups();
+        if !problems.is_empty() {
+            return Err(Error::Integrity {
+                problems: problems.len(),
+            });
+        }
+
+        Ok(data)
+    }
+
+    /// Reads the project data without checking it for integrity problems
+    pub fn load_unchecked(&self) -> Result<Project> {
+        self.load_migrated().map(|(data, _)| data)
+    }
+
+    /// Reads the project data, also returning the migrations that were needed to
+    /// upgrade it, the upgraded data is only written on the next save
+    pub fn load_migrated(&self) -> Result<(Project, Vec<Applied>)> {
+        let content = fs::read_to_string(&self.path)?;
+        let mut value: serde_json::Value = serde_json::from_str(&content)?;
+        let applied = migrate::migrate(&mut value)?;
+
+        // parsing the original text keeps the positions in error messages
+        let data = if applied.is_empty() {
+            serde_json::from_str(&content)?
+        } else {
+            serde_json::from_value(value)?
+        };
+
+        Ok((data, applied))
+    }
+
+    /// Writes the project data, the project file is replaced atomically so
+    /// it's never left half written, and the previous version is kept as a backup
+    pub fn save(&self, data: &Project) -> Result<()> {
+        let content = serde_json::to_string_pretty(data)?;
+        self.write_atomically(&self.path, content.as_bytes(), Some(&self.backup_path()))
+    }
+
+    /// Reads the history of changes, a project without one has an empty history
+    pub fn load_history(&self) -> Result<History> {
+        match fs::read_to_string(self.history_path()) {
+            Ok(content) => Ok(serde_json::from_str(&content)?),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(History::default()),
+            Err(e) => Err(Error::Io(e)),
+        }
+    }
+
+    pub fn save_history(&self, history: &History) -> Result<()> {
+        let content = serde_json::to_string(history)?;
+        self.write_atomically(&self.history_path(), content.as_bytes(), None)
+    }
+
+    /// Adds a change to the history, the project has to be locked
+    pub fn record(&self, summary: String, before: Project, after: Project) -> Result<()> {
+        // a history that can't be read is only lost, it never blocks a change
+        let mut history = self.load_history().unwrap_or_default();
+        history.record(summary, before, after);
+        self.save_history(&history)
+    }
+
+    /// Replaces `target` with `content` through a temporary file, so it's never left half
+    /// written, copying the previous version to `backup` first if it's given
+    fn write_atomically(&self, target: &Path, content: &[u8], backup: Option<&Path>) -> Result<()> {
+        let temp_path = self.with_suffix(&format!(".{}.tmp", process::id()));
+
+        let result = replace_with(&temp_path, target, content, backup);
+        if result.is_err() {
+            let _ = fs::remove_file(&temp_path);
+        }
+
+        result.map_err(Error::Io)
+    }
+}
+
+fn replace_with(
+    temp_path: &Path,
+    target: &Path,
+    content: &[u8],
+    backup: Option<&Path>,
+) -> io::Result<()> {
+    let mut file = File::create(temp_path)?;
+    file.write_all(content)?;
+    file.sync_all()?;
+
+    if let Some(backup) = backup.filter(|_| target.exists()) {
+        fs::copy(target, backup)?;
+    }
+    fs::rename(temp_path, target)?;
+
+    // the rename is only durable once the directory itself is synced
+    #[cfg(unix)]
+    if let Some(dir) = target.parent() {
+        let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+        File::open(dir)?.sync_all()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,81 @@
+use chrono::{DateTime, Datelike, Days, Local, NaiveDate, Utc, Weekday};
+
+/// Returns the current local date
+pub fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+/// Parses a date relative to `today`, accepts ISO dates (`2024-10-31`), `today`,
+/// `tomorrow`, offsets like `+3d`, `+2w` or `-7d` and weekday names like `friday` or `fri`
+pub fn parse_date(input: &str, today: NaiveDate) -> Result<NaiveDate, String> {
+    let input = input.trim().to_lowercase();
+
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Ok(date);
+    }
+
+    match input.as_str() {
+        "today" => return Ok(today),
+        "tomorrow" => return Ok(today + Days::new(1)),
+        _ => (),
+    }
+
+    let (forward, offset) = match (input.strip_prefix('+'), input.strip_prefix('-')) {
+        (Some(offset), _) => (true, Some(offset)),
+        (_, Some(offset)) => (false, Some(offset)),
+        _ => (false, None),
+    };
+    if let Some(offset) = offset {
+        // counts too big for a date are invalid, just like unknown units
+        let days = if let Some(count) = offset.strip_suffix('d') {
+            count.parse::<u64>().ok()
+        } else if let Some(count) = offset.strip_suffix('w') {
+            count
+                .parse::<u64>()
+                .ok()
+                .and_then(|count| count.checked_mul(7))
+        } else {
+            None
+        };
+
+        let date = days.and_then(|days| {
+            if forward {
+                today.checked_add_days(Days::new(days))
+            } else {
+                today.checked_sub_days(Days::new(days))
+            }
+        });
+        if let Some(date) = date {
+            return Ok(date);
+        }
+    }
+
+    if let Ok(weekday) = input.parse::<Weekday>() {
+        // always the upcoming one, so `friday` on a friday means a week later
+        let days_ahead = (weekday.num_days_from_monday() + 7
+            - today.weekday().num_days_from_monday())
+            % 7;
+        let days_ahead = if days_ahead == 0 { 7 } else { days_ahead };
+        return Ok(today + Days::new(days_ahead as u64));
+    }
+
+    Err(format!(
+        "Invalid date `{}` (use YYYY-MM-DD, today, tomorrow, +3d, -2w or a weekday)",
+        input
+    ))
+}
+
+/// Clap value parser for dates relative to the current date
+pub fn parse_date_arg(input: &str) -> Result<NaiveDate, String> {
+    parse_date(input, today())
+}
+
+/// Returns the local date of a timestamp
+pub fn local_date(time: DateTime<Utc>) -> NaiveDate {
+    time.with_timezone(&Local).date_naive()
+}
+
+/// Formats a timestamp in local time
+pub fn format_time(time: DateTime<Utc>) -> String {
+    time.with_timezone(&Local).format("%Y-%m-%d %H:%M").to_string()
+}
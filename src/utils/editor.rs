@@ -0,0 +1,57 @@
+use crate::Error;
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+use std::{env, fs, io, process};
+
+/// Opens the user's editor on `text` and returns what it was changed to
+///
+/// `$VISUAL` is preferred over `$EDITOR`, and `vi` is used if neither is set. The editor
+/// command can include arguments, like `code --wait`.
+pub fn edit_text(text: &str) -> Result<String, Error> {
+    let editor = env::var("VISUAL")
+        .or_else(|_| env::var("EDITOR"))
+        .unwrap_or_else(|_| "vi".to_string());
+    let mut args = editor.split_whitespace();
+    let Some(program) = args.next() else {
+        return Err(Error::Usage("The editor command is empty".to_string()));
+    };
+
+    // the temporary directory is shared, so the file must be new, never one that's already there
+    // (like a link someone else put in place), and only readable by the user
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| d.subsec_nanos());
+    let path = env::temp_dir().join(format!("pmgr-edit-{}-{}.txt", process::id(), nanos));
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&path)?;
+    let written = writeln!(file, "{}", text);
+    drop(file);
+    if let Err(error) = written {
+        let _ = fs::remove_file(&path);
+        return Err(error.into());
+    }
+
+    let status = process::Command::new(program)
+        .args(args)
+        .arg(&path)
+        .status();
+    let edited = status.and_then(|status| {
+        if status.success() {
+            fs::read_to_string(&path)
+        } else {
+            Err(io::Error::other(format!(
+                "Editor `{}` exited with {}",
+                editor, status
+            )))
+        }
+    });
+    let _ = fs::remove_file(&path);
+
+    Ok(edited?.trim_end_matches(['\n', '\r']).to_string())
+}
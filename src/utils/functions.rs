@@ -1,15 +1,19 @@
-use clap::{error::ErrorKind, CommandFactory};
-
-use super::ExpectWith;
-use crate::{data::Project, Cli};
+use super::today;
+use crate::{
+    data::{Note, Priority, Project, Task, TaskState},
+    fg_color, Error, Store,
+};
+use clap::builder::styling;
 
 use std::{
-    env,
-    fs::{self, File},
-    io,
+    env, io,
     path::{Path, PathBuf},
+    time::Duration,
 };
 
+/// How long to wait for another pmgr process to release the project
+pub const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+
 pub fn check_data(file_name: &str) -> Result<PathBuf, io::Error> {
     let current_dir = env::current_dir()?;
 
@@ -33,36 +37,133 @@ fn check_data_with_path(path: &Path, file_name: &str) -> Option<PathBuf> {
         .and_then(|p| check_data_with_path(p, file_name))
 }
 
-pub fn get_data(file_name: &str) -> Option<Project> {
-    let file = check_data(file_name).and_then(File::open);
+pub fn get_data(file_name: &str) -> Result<Project, Error> {
+    Store::find(file_name)?.load()
+}
 
-    match file {
-        Ok(path) => {
-            let data = serde_json::from_reader(path).expect_with("Failed to read project data");
+pub fn write_data(file_name: &str, data: &Project) -> Result<(), Error> {
+    let store = Store::find(file_name)?;
+    let _lock = store.lock(LOCK_TIMEOUT)?;
 
-            Some(data)
-        }
-        Err(e) => {
-            let _ = Cli::command()
-                .error(
-                    ErrorKind::Io,
-                    format!(
-                        "Failed to get project root: {}",
-                        e
-                    ),
-                )
-                .print();
-            None
-        }
+    store.save(data)
+}
+
+/// Loads the project data, changes it with `f` and writes it back,
+/// the project stays locked the whole time so no concurrent change is lost
+///
+/// The change is added to the history as `summary`, which is shown by `pmgr history`.
+pub fn update_data<T>(
+    file_name: &str,
+    summary: &str,
+    f: impl FnOnce(&mut Project) -> Result<T, Error>,
+) -> Result<T, Error> {
+    update_data_summarized(file_name, |data| {
+        f(data).map(|value| (value, summary.to_string()))
+    })
+}
+
+/// Like `update_data`, for changes whose summary depends on the data, `f` returns the summary
+pub fn update_data_summarized<T>(
+    file_name: &str,
+    f: impl FnOnce(&mut Project) -> Result<(T, String), Error>,
+) -> Result<T, Error> {
+    let store = Store::find(file_name)?;
+    let _lock = store.lock(LOCK_TIMEOUT)?;
+
+    let mut data = store.load()?;
+    let before = data.clone();
+    let (value, summary) = f(&mut data)?;
+    store.save(&data)?;
+    record_change(&store, summary, before, data);
+
+    Ok(value)
+}
+
+/// Adds a saved change to the history, only warning if that fails
+///
+/// The change is already saved at this point, failing the command would report it as not made.
+pub fn record_change(store: &Store, summary: String, before: Project, after: Project) {
+    if let Err(error) = store.record(summary, before, after) {
+        println!(
+            "{} the change was saved, but it couldn't be added to the history: {}",
+            fg_color!("Warning:", Red),
+            error
+        );
+    }
+}
+
+/// Formats a list of task or note IDs separated by commas
+pub fn format_ids(ids: &[usize]) -> String {
+    ids.iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
+
+/// Formats a task's text coloured by its priority, with the priority shown if it's not normal,
+/// overdue tasks are always shown in red
+pub fn format_task(task: &Task) -> String {
+    format!("{}{}", format_task_text(task), format_tags(&task.tags))
+}
+
+/// Formats a note's text followed by its tags
+pub fn format_note(note: &Note) -> String {
+    format!("{}{}", note.note, format_tags(&note.tags))
+}
+
+/// Formats a list of tags to be shown after an item's text
+pub fn format_tags(tags: &[String]) -> String {
+    if tags.is_empty() {
+        return String::new();
+    }
+
+    let tags: Vec<String> = tags.iter().map(|t| format!("#{}", t)).collect();
+    format!(" {}", fg_color!(tags.join(" "), Cyan))
+}
+
+fn format_task_text(task: &Task) -> String {
+    let mut text = task.task.clone();
+    if task.priority != Priority::Normal {
+        text = format!("{} ({})", text, task.priority);
+    }
+    if let Some(due) = task.due {
+        text = format!("{} (due {})", text, due);
+    }
+    if let TaskState::Blocked { reason: Some(reason) } = &task.state {
+        text = format!("{} (blocked: {})", text, reason);
+    }
+
+    if task.state == TaskState::Cancelled {
+        return fg_color!(text, BrightBlack);
+    }
+    if task.is_overdue(today()) {
+        return fg_color!(format!("{} (overdue)", text), Red);
+    }
+
+    match task.priority {
+        Priority::Low => fg_color!(text, BrightBlack),
+        Priority::Normal => text,
+        Priority::High => fg_color!(text, Magenta),
+        Priority::Urgent => fg_color!(text, Red),
     }
 }
 
-pub fn write_data(file_name: &str, data: &Project) {
-    let path = check_data(file_name).expect_with("Failed to open the project file");
+/// Clap value parser for tags, a leading `#` is dropped
+pub fn parse_tag(input: &str) -> Result<String, String> {
+    let tag = input.trim().trim_start_matches('#');
+
+    if tag.is_empty() || tag.contains(char::is_whitespace) {
+        return Err(format!("Invalid tag `{}`, tags can't be empty or contain spaces", input));
+    }
+
+    Ok(tag.to_string())
+}
 
-    fs::write(
-        path,
-        serde_json::to_string_pretty(data).expect("Failed to serialize project data"),
-    )
-    .expect("Failed to write project data");
+/// Adds tags to a list of tags, skipping the ones that it already has
+pub fn merge_tags(tags: &mut Vec<String>, new_tags: &[String]) {
+    for tag in new_tags {
+        if !tags.contains(tag) {
+            tags.push(tag.to_string());
+        }
+    }
 }
@@ -1,8 +1,14 @@
+mod dates;
+mod editor;
 mod functions;
 mod macros;
+mod prompt;
 
 use std::fmt::Debug;
+pub use dates::*;
+pub use editor::*;
 pub use functions::*;
+pub use prompt::*;
 
 pub trait ExpectWith<T, E> {
     fn expect_with(self, msg: &str) -> T;
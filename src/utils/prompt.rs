@@ -0,0 +1,22 @@
+use crate::Error;
+use std::io::{self, IsTerminal, Write};
+
+/// Asks a yes or no question on the terminal, anything but `y` or `yes` is taken as no
+///
+/// Without a terminal there's no one to answer, so this fails instead of guessing.
+pub fn confirm(question: &str) -> Result<bool, Error> {
+    if !io::stdin().is_terminal() {
+        return Err(Error::Usage(
+            "Confirmation is needed, but there is no terminal to ask on (Use --yes flag to skip it)"
+                .to_string(),
+        ));
+    }
+
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
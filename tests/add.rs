@@ -3,7 +3,6 @@ mod common;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use common;
     use pmgr::{
         commands,
         data::{Group, Note, Project, Task, TaskState},
@@ -14,7 +13,7 @@ mod tests {
     fn simple_add() {
         let file_name = ".simple-add.pmgr";
         common::clean(file_name);
-        commands::init::InitArgs.run(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
 
         create_groups_local!(
             project,
@@ -26,14 +25,22 @@ mod tests {
         add_tasks_local!(
             project,
             "group1",
-            TaskState::Incomplete -> "task1",
-            TaskState::Incomplete -> "task2",
-            TaskState::Incomplete -> "task3",
-            TaskState::Incomplete -> "task4",
-            TaskState::Incomplete -> "task5",
+            1: TaskState::Incomplete -> "task1",
+            2: TaskState::Incomplete -> "task2",
+            3: TaskState::Incomplete -> "task3",
+            4: TaskState::Incomplete -> "task4",
+            5: TaskState::Incomplete -> "task5",
         );
 
-        add_notes_local!(project, "group3", "note1", "note2", "note3", "note4", "note5",);
+        add_notes_local!(
+            project,
+            "group3",
+            1: "note1",
+            2: "note2",
+            3: "note3",
+            4: "note4",
+            5: "note5",
+        );
 
         create_groups!(
             file_name,
@@ -46,10 +53,11 @@ mod tests {
 
         add_notes!(file_name, "group3", "note1", "note2", "note3", "note4", "note5",);
 
-        let Some(data) = utils::get_data(file_name) else {
+        let Ok(mut data) = utils::get_data(file_name) else {
             panic!("Failed to get data");
         };
 
+        clear_timestamps!(data, project);
         assert_eq!(data.groups, project.groups);
 
         common::clean(file_name);
@@ -0,0 +1,149 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{
+        commands::{
+            self,
+            archive::{ArchiveArgs, UnarchiveArgs},
+            watch::WatchArgs,
+        },
+        query, utils, Command, Error, Query,
+    };
+
+    fn archive(file_name: &str, group_name: &str) -> Result<(), Error> {
+        ArchiveArgs {
+            group_name: group_name.to_string(),
+        }
+        .run(file_name)
+    }
+
+    fn unarchive(file_name: &str, group_name: &str) -> Result<(), Error> {
+        UnarchiveArgs {
+            group_name: group_name.to_string(),
+        }
+        .run(file_name)
+    }
+
+    fn including_archived() -> Query {
+        Query {
+            include_archived: true,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn archive_hides_groups() {
+        let file_name = ".archive-hides-groups.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "milestone1" -> ["sprint1"],
+            "milestone2" -> [],
+        );
+        watch_groups!(file_name, true, "milestone1");
+        archive(file_name, "milestone1").unwrap();
+
+        let data = utils::get_data(file_name).unwrap();
+        assert!(data.is_archived("sprint1"));
+        assert!(!data.is_archived("milestone2"));
+
+        let all = query::select_groups(&data, None, true, &Query::default()).unwrap();
+        assert_eq!(all, vec!["milestone2"]);
+        let all = query::select_groups(&data, None, true, &including_archived()).unwrap();
+        assert_eq!(all, vec!["milestone1", "milestone2", "sprint1"]);
+
+        // naming an archived group is asking for it
+        let named = query::select_groups(&data, Some("milestone1"), false, &Query::default());
+        assert_eq!(named.unwrap(), vec!["milestone1", "sprint1"]);
+
+        let Err(Error::Invalid(_)) = query::select_groups(&data, None, false, &Query::default())
+        else {
+            panic!("Archived watched groups were selected");
+        };
+        let watched = query::select_groups(&data, None, false, &including_archived()).unwrap();
+        assert_eq!(watched, vec!["milestone1", "sprint1"]);
+
+        unarchive(file_name, "milestone1").unwrap();
+        let data = utils::get_data(file_name).unwrap();
+        let watched = query::select_groups(&data, None, false, &Query::default()).unwrap();
+        assert_eq!(watched, vec!["milestone1", "sprint1"]);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn archive_watch() {
+        let file_name = ".archive-watch.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "project" -> ["done", "doing"],
+        );
+        archive(file_name, "done").unwrap();
+
+        let watch = |group_name: &str, include_archived: bool| {
+            WatchArgs {
+                group_names: vec![group_name.to_string()],
+                include_archived,
+            }
+            .run(file_name)
+        };
+
+        let Err(Error::Invalid(_)) = watch("done", false) else {
+            panic!("An archived group was watched");
+        };
+
+        // archived sub-groups are skipped when their parent is watched
+        watch("project", false).unwrap();
+        let data = utils::get_data(file_name).unwrap();
+        assert_eq!(data.active_groups, vec!["doing", "project"]);
+
+        watch("done", true).unwrap();
+        let data = utils::get_data(file_name).unwrap();
+        assert_eq!(data.active_groups, vec!["doing", "done", "project"]);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn archive_errors() {
+        let file_name = ".archive-errors.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "group1" -> ["group2"],
+        );
+
+        let Err(Error::Missing(_)) = archive(file_name, "group3") else {
+            panic!("A group that doesn't exist was archived");
+        };
+        let Err(Error::Invalid(_)) = unarchive(file_name, "group1") else {
+            panic!("A group that isn't archived was unarchived");
+        };
+
+        archive(file_name, "group1").unwrap();
+        let Err(Error::Invalid(_)) = archive(file_name, "group1") else {
+            panic!("A group was archived twice");
+        };
+        let Err(Error::Invalid(_)) = archive(file_name, "group2") else {
+            panic!("A group under an archived group was archived");
+        };
+        let Err(Error::Invalid(_)) = unarchive(file_name, "group2") else {
+            panic!("A group was unarchived while its parent is archived");
+        };
+
+        let data = utils::get_data(file_name).unwrap();
+        assert!(data.groups["group1"].archived);
+        assert!(!data.groups["group2"].archived);
+
+        common::clean(file_name);
+    }
+}
@@ -3,7 +3,6 @@ mod common;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use common;
     use pmgr::{commands, utils, Command};
     use std::io;
 
@@ -13,12 +12,11 @@ mod tests {
         common::clean(file_name);
 
         // data doesn't exist for this check
-        match utils::check_data(file_name) {
-            Ok(data) => panic!("Data shouldn't exist, exists at: {:?}", data),
-            Err(_) => (),
+        if let Ok(data) = utils::check_data(file_name) {
+            panic!("Data shouldn't exist, exists at: {:?}", data);
         }
 
-        commands::init::InitArgs.run(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
 
         // data exists for this check
         match utils::check_data(file_name) {
@@ -1,4 +1,8 @@
-use std::fs;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 /// Creates a list of groups in a project file
 #[macro_export]
@@ -11,12 +15,12 @@ macro_rules! create_groups {
             commands::create::CreateArgs {
                 group_name: $group.to_string(),
                 parent_group: None,
-            }.run($file_name);
+            }.run($file_name).ok();
             $(
                 commands::create::CreateArgs {
                     group_name: $subgroup.to_string(),
                     parent_group: Some($group.to_string()),
-                }.run($file_name);
+                }.run($file_name).ok();
             )*
         )*
     };
@@ -47,8 +51,9 @@ macro_rules! delete_groups {
     ) => {
         $(println!("group name: {}", $group);)*
         commands::delete::DeleteArgs {
-            group_names: vec![$($group.to_string(),)*]
-        }.run($file_name);
+            group_names: vec![$($group.to_string(),)*],
+            confirmation: commands::Confirmation { yes: true, dry_run: false },
+        }.run($file_name).ok();
     };
 }
 
@@ -64,7 +69,8 @@ macro_rules! add_tasks {
             commands::add::AddTaskArgs {
                 group_name: $group.to_string(),
                 text: vec![$task.to_string()],
-            }.run($file_name);
+                ..Default::default()
+            }.run($file_name).ok();
         )*
     };
 }
@@ -75,12 +81,12 @@ macro_rules! add_tasks_local {
     (
         $project:ident,
         $group:literal,
-        $(TaskState::$state:ident -> $task:literal$(,)?)*
+        $($id:literal: TaskState::$state:ident -> $task:literal$(,)?)*
     ) => {
         $(
             $project.groups.get_mut($group).unwrap().tasks.push(Task {
-                task: $task.to_string(),
                 state: TaskState::$state,
+                ..Task::new($id, $task)
             });
         )*
     };
@@ -95,7 +101,8 @@ macro_rules! remove_tasks {
         commands::remove::RemoveTaskArgs {
             group_name: $group_name.to_string(),
             ids: vec![$($id,)*],
-        }.run($file_name);
+            confirmation: commands::Confirmation { yes: true, dry_run: false },
+        }.run($file_name).ok();
     };
 }
 
@@ -110,7 +117,9 @@ macro_rules! complete_tasks {
         commands::task::TaskCompleteArgs {
             group_name: $group_name.to_string(),
             ids: vec![$($id,)*],
-        }.run($file_name);
+            cascade: false,
+            force: false,
+        }.run($file_name).ok();
     };
     (
         $file_name:ident,
@@ -120,7 +129,7 @@ macro_rules! complete_tasks {
         commands::task::TaskUndoArgs {
             group_name: $group_name.to_string(),
             ids: vec![$($id,)*],
-        }.run($file_name);
+        }.run($file_name).ok();
     };
 }
 
@@ -136,7 +145,8 @@ macro_rules! add_notes {
             commands::add::AddNoteArgs {
                 group_name: $group.to_string(),
                 text: vec![$note.to_string()],
-            }.run($file_name);
+                ..Default::default()
+            }.run($file_name).ok();
         )*
     };
 }
@@ -147,12 +157,10 @@ macro_rules! add_notes_local {
     (
         $project:ident,
         $group:literal,
-        $($note:literal$(,)?)*
+        $($id:literal: $note:literal$(,)?)*
     ) => {
         $(
-            $project.groups.get_mut($group).unwrap().notes.push(Note {
-                note: $note.to_string(),
-            });
+            $project.groups.get_mut($group).unwrap().notes.push(Note::new($id, $note));
         )*
     };
 }
@@ -166,7 +174,28 @@ macro_rules! remove_notes {
         commands::remove::RemoveNoteArgs {
             group_name: $group_name.to_string(),
             ids: vec![$($id,)*],
-        }.run($file_name);
+            confirmation: commands::Confirmation { yes: true, dry_run: false },
+        }.run($file_name).ok();
+    };
+}
+
+/// Clears the timestamps of all tasks and notes in a project variable, so it can be compared
+#[macro_export]
+macro_rules! clear_timestamps {
+    (
+        $($project:ident$(,)?)*
+    ) => {
+        $(
+            for group in $project.groups.values_mut() {
+                group.for_each_task_mut(&mut |task| {
+                    task.created_at = None;
+                    task.completed_at = None;
+                });
+                for note in &mut group.notes {
+                    note.created_at = None;
+                }
+            }
+        )*
     };
 }
 
@@ -192,8 +221,9 @@ macro_rules! watch_groups {
         $file_name:ident, true, $($group:literal$(,)?)*
     ) =>{
         $(commands::watch::WatchArgs {
-            group_names: vec![$group.to_string()]
-        }.run($file_name);)*
+            group_names: vec![$group.to_string()],
+            include_archived: false,
+        }.run($file_name).ok();)*
     };
     (
         $file_name:ident, false, $($group:literal$(,)?)*
@@ -201,16 +231,58 @@ macro_rules! watch_groups {
         $(commands::unwatch::UnwatchArgs {
             group_names: vec![$group.to_string()],
             all: false,
-        }.run($file_name);)*
+            confirmation: commands::Confirmation::default(),
+        }.run($file_name).ok();)*
     };
 }
 
-/// Ensures that the specified file_name does not exist, and if it does, gets removed
+/// Ensures that the specified file_name (and its backup) does not exist, and if it does, gets removed
+#[allow(dead_code)]
 pub fn clean(file_name: &str) {
-    let file = fs::metadata(file_name).is_ok();
-    if file {
-        if let Err(e) = fs::remove_file(file_name) {
-            panic!("{}", e);
+    for file_name in [
+        file_name.to_string(),
+        format!("{}.bak", file_name),
+        format!("{}.history", file_name),
+    ] {
+        let file = fs::metadata(&file_name).is_ok();
+        if file {
+            if let Err(e) = fs::remove_file(&file_name) {
+                panic!("{}", e);
+            }
         }
     }
 }
+
+/// Creates an empty directory outside of any project, for tests that run the pmgr binary
+#[allow(dead_code)]
+pub fn project_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(name);
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Runs the pmgr binary in `dir`, returns its exit code
+#[allow(dead_code)]
+pub fn pmgr_code(dir: &Path, args: &[&str]) -> i32 {
+    Command::new(env!("CARGO_BIN_EXE_pmgr"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to run pmgr")
+        .status
+        .code()
+        .expect("pmgr was terminated by a signal")
+}
+
+/// Runs the pmgr binary in `dir`, returns what it printed and fails if it didn't succeed
+#[allow(dead_code)]
+pub fn pmgr_stdout(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new(env!("CARGO_BIN_EXE_pmgr"))
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .expect("Failed to run pmgr");
+    assert!(output.status.success(), "pmgr {:?} failed", args);
+    String::from_utf8(output.stdout).unwrap()
+}
@@ -3,7 +3,6 @@ mod common;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use common;
     use pmgr::{
         commands,
         data::{Group, Project},
@@ -22,7 +21,7 @@ mod tests {
             group3 -> [],
         );
 
-        commands::init::InitArgs.run(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
 
         create_groups!(
             file_name,
@@ -33,7 +32,7 @@ mod tests {
             "group3" -> [],
         );
 
-        let Some(data) = utils::get_data(file_name) else {
+        let Ok(data) = utils::get_data(file_name) else {
             panic!("Failed to get data");
         };
         assert_eq!(data, project);
@@ -55,7 +54,7 @@ mod tests {
             group5 -> [],
         );
 
-        commands::init::InitArgs.run(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
 
         create_groups!(
             file_name,
@@ -64,7 +63,7 @@ mod tests {
             "group4" -> ["group5"],
         );
 
-        let Some(data) = utils::get_data(file_name) else {
+        let Ok(data) = utils::get_data(file_name) else {
             panic!("Failed to get data");
         };
         assert_eq!(data, project);
@@ -3,7 +3,6 @@ mod common;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use common;
     use pmgr::{
         commands,
         data::{Group, Project},
@@ -15,7 +14,7 @@ mod tests {
         let file_name = ".simple-delete.pmgr";
         common::clean(file_name);
 
-        commands::init::InitArgs.run(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
 
         create_groups!(
             file_name,
@@ -33,7 +32,7 @@ mod tests {
             group3 -> [],
         );
 
-        let Some(data) = utils::get_data(file_name) else {
+        let Ok(data) = utils::get_data(file_name) else {
             panic!("Failed to get data");
         };
         assert_eq!(data.groups, project.groups);
@@ -43,11 +42,64 @@ mod tests {
 
         delete_groups!(file_name, "group3");
 
-        let Some(data) = utils::get_data(file_name) else {
+        let Ok(data) = utils::get_data(file_name) else {
             panic!("Failed to get data");
         };
         assert_eq!(data.groups, project.groups);
 
         common::clean(file_name);
     }
+
+    #[test]
+    fn delete_dry_run() {
+        let file_name = ".delete-dry-run.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> ["group2"],);
+        let before = utils::get_data(file_name).unwrap();
+
+        commands::delete::DeleteArgs {
+            group_names: vec!["group1".to_string()],
+            confirmation: commands::Confirmation {
+                yes: false,
+                dry_run: true,
+            },
+        }
+        .run(file_name)
+        .unwrap();
+
+        assert_eq!(utils::get_data(file_name).unwrap(), before);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn delete_group_and_descendant() {
+        let file_name = ".delete-group-and-descendant.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "group1" -> ["group2"],
+            "group2" -> ["group3"],
+            "group4" -> [],
+        );
+        watch_groups!(file_name, true, "group3");
+
+        // group2 is also deleted as a descendant of group1
+        delete_groups!(file_name, "group2", "group1");
+
+        let mut project = Project::new();
+        project
+            .groups
+            .insert("group4".to_string(), Group::new("group4"));
+
+        let data = utils::get_data(file_name).unwrap();
+        assert_eq!(data.groups, project.groups);
+        assert!(data.active_groups.is_empty());
+
+        common::clean(file_name);
+    }
 }
@@ -0,0 +1,242 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{
+        commands::{
+            self,
+            task::{TaskCompleteArgs, TaskLinkArgs, TaskUnlinkArgs},
+        },
+        data::{Link, TaskState},
+        utils, Command, Error,
+    };
+
+    fn link(
+        file_name: &str,
+        group_name: &str,
+        id: usize,
+        blocked_by: &[usize],
+    ) -> Result<(), Error> {
+        TaskLinkArgs {
+            group_name: group_name.to_string(),
+            id,
+            blocked_by: blocked_by.to_vec(),
+        }
+        .run(file_name)
+    }
+
+    fn complete(file_name: &str, group_name: &str, id: usize, force: bool) -> Result<(), Error> {
+        TaskCompleteArgs {
+            group_name: group_name.to_string(),
+            ids: vec![id],
+            cascade: false,
+            force,
+        }
+        .run(file_name)
+    }
+
+    #[test]
+    fn deps_chain() {
+        let file_name = ".deps-chain.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "db" -> [], "api" -> [],);
+        add_tasks!(file_name, "db", "backup", "migrate");
+        add_tasks!(file_name, "api", "deploy", "announce");
+
+        link(file_name, "db", 2, &[1]).unwrap();
+        link(file_name, "api", 3, &[2]).unwrap();
+        link(file_name, "api", 4, &[3, 3]).unwrap();
+
+        let data = utils::get_data(file_name).unwrap();
+        assert_eq!(data.find_task(4).unwrap().1.blocked_by, vec![3]);
+        assert_eq!(
+            data.upstream(4),
+            vec![
+                Link { id: 3, depth: 1 },
+                Link { id: 2, depth: 2 },
+                Link { id: 1, depth: 3 },
+            ]
+        );
+        assert_eq!(
+            data.downstream(1),
+            vec![
+                Link { id: 2, depth: 1 },
+                Link { id: 3, depth: 2 },
+                Link { id: 4, depth: 3 },
+            ]
+        );
+        assert_eq!(data.dependents(3), vec![4]);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn deps_reject_cycle() {
+        let file_name = ".deps-reject-cycle.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "db" -> [], "api" -> [],);
+        add_tasks!(file_name, "db", "migrate");
+        add_tasks!(file_name, "api", "deploy", "announce");
+
+        link(file_name, "api", 2, &[1]).unwrap();
+        link(file_name, "api", 3, &[2]).unwrap();
+        let before = utils::get_data(file_name).unwrap();
+
+        let Err(Error::Invalid(_)) = link(file_name, "db", 1, &[3]) else {
+            panic!("Linked tasks in a cycle");
+        };
+        let Err(Error::Invalid(_)) = link(file_name, "db", 1, &[1]) else {
+            panic!("Linked a task to itself");
+        };
+        let Err(Error::Missing(_)) = link(file_name, "db", 1, &[9]) else {
+            panic!("Linked a task to a task that doesn't exist");
+        };
+        let Err(Error::Missing(_)) = link(file_name, "db", 2, &[1]) else {
+            panic!("Linked a task that isn't in the group");
+        };
+        assert_eq!(utils::get_data(file_name).unwrap(), before);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn deps_complete_blocked() {
+        let file_name = ".deps-complete-blocked.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "db" -> [], "api" -> [],);
+        add_tasks!(file_name, "db", "migrate");
+        add_tasks!(file_name, "api", "deploy");
+        link(file_name, "api", 2, &[1]).unwrap();
+
+        let Err(Error::Invalid(_)) = complete(file_name, "api", 2, false) else {
+            panic!("Completed a task that waits on an unfinished task");
+        };
+
+        // once the task it waits on is finished, nothing is in the way
+        complete(file_name, "db", 1, false).unwrap();
+        complete(file_name, "api", 2, false).unwrap();
+
+        let data = utils::get_data(file_name).unwrap();
+        assert_eq!(data.find_task(2).unwrap().1.state, TaskState::Complete);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn deps_complete_together() {
+        let file_name = ".deps-complete-together.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+        add_tasks!(file_name, "group1", "migrate", "deploy");
+        link(file_name, "group1", 2, &[1]).unwrap();
+
+        // the check sees the tasks as they are after the change
+        TaskCompleteArgs {
+            group_name: "group1".to_string(),
+            ids: vec![2, 1],
+            cascade: false,
+            force: false,
+        }
+        .run(file_name)
+        .unwrap();
+
+        let data = utils::get_data(file_name).unwrap();
+        assert_eq!(data.find_task(2).unwrap().1.state, TaskState::Complete);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn deps_complete_force() {
+        let file_name = ".deps-complete-force.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+        add_tasks!(file_name, "group1", "migrate", "deploy");
+        link(file_name, "group1", 2, &[1]).unwrap();
+
+        complete(file_name, "group1", 2, true).unwrap();
+
+        let data = utils::get_data(file_name).unwrap();
+        assert_eq!(data.find_task(2).unwrap().1.state, TaskState::Complete);
+        assert_eq!(
+            data.unfinished_blockers(data.find_task(2).unwrap().1),
+            vec![1]
+        );
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn deps_parent_not_auto_completed() {
+        let file_name = ".deps-parent-not-auto-completed.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+        add_tasks!(file_name, "group1", "migrate", "deploy");
+        commands::add::AddTaskArgs {
+            group_name: "group1".to_string(),
+            text: vec!["build".to_string()],
+            parent: Some(2),
+            ..Default::default()
+        }
+        .run(file_name)
+        .unwrap();
+        link(file_name, "group1", 2, &[1]).unwrap();
+
+        complete(file_name, "group1", 3, false).unwrap();
+
+        let data = utils::get_data(file_name).unwrap();
+        assert_eq!(data.find_task(3).unwrap().1.state, TaskState::Complete);
+        assert_eq!(data.find_task(2).unwrap().1.state, TaskState::Incomplete);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn deps_unlink_and_remove() {
+        let file_name = ".deps-unlink-and-remove.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "db" -> [], "api" -> [],);
+        add_tasks!(file_name, "db", "backup", "migrate");
+        add_tasks!(file_name, "api", "deploy");
+        link(file_name, "api", 3, &[1, 2]).unwrap();
+
+        let Err(Error::Invalid(_)) = TaskUnlinkArgs {
+            group_name: "api".to_string(),
+            id: 3,
+            blocked_by: vec![1, 3],
+        }
+        .run(file_name) else {
+            panic!("Unlinked a task that wasn't linked");
+        };
+
+        TaskUnlinkArgs {
+            group_name: "api".to_string(),
+            id: 3,
+            blocked_by: vec![1],
+        }
+        .run(file_name)
+        .unwrap();
+
+        // links to removed tasks are removed with them
+        remove_tasks!(file_name, "db", 2);
+        let data = utils::get_data(file_name).unwrap();
+        assert!(data.find_task(3).unwrap().1.blocked_by.is_empty());
+
+        common::clean(file_name);
+    }
+}
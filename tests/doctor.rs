@@ -0,0 +1,189 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::data::{Problem, Project};
+    use pmgr::{commands, utils, Command, Error, Store};
+    use std::fs;
+
+    /// A hand-edited project with every kind of problem the doctor knows about
+    const BROKEN_PROJECT: &str = r#"{
+        "schema_version": 1,
+        "information": {"title": null, "description": null, "repo": null},
+        "active_groups": ["a", "gone", "a"],
+        "groups": {
+            "a": {"name": "a", "notes": [], "tasks": [], "groups": ["b", "ghost", "b"]},
+            "b": {"name": "b", "notes": [], "tasks": [], "groups": ["c"]},
+            "c": {"name": "c", "notes": [], "tasks": [], "groups": ["a"]},
+            "d": {"name": "d", "notes": [], "tasks": [], "groups": ["e"]},
+            "e": {"name": "renamed", "notes": [], "tasks": [], "groups": []},
+            "f": {
+                "name": "f",
+                "notes": [{"id": 1, "note": "note1"}, {"id": 1, "note": "note2"}],
+                "tasks": [
+                    {"id": 4, "task": "task1", "state": "Incomplete"},
+                    {"id": 4, "task": "task2", "state": "Complete"}
+                ],
+                "groups": ["e"]
+            },
+            "g": {
+                "name": "g",
+                "notes": [],
+                "tasks": [
+                    {"id": 1, "task": "task3", "state": "Incomplete", "blocked_by": [2]},
+                    {"id": 2, "task": "task4", "state": "Incomplete", "blocked_by": [1]},
+                    {"id": 3, "task": "task5", "state": "Incomplete", "blocked_by": [9, 1]}
+                ],
+                "groups": []
+            }
+        },
+        "last_task_id": 2,
+        "last_note_id": 1
+    }"#;
+
+    fn broken_project() -> Project {
+        serde_json::from_str(BROKEN_PROJECT).unwrap()
+    }
+
+    fn strings(names: &[&str]) -> Vec<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn doctor_check() {
+        let problems = broken_project().check();
+
+        assert_eq!(
+            problems,
+            vec![
+                Problem::MissingChild {
+                    parent: "a".to_string(),
+                    child: "ghost".to_string(),
+                },
+                Problem::DuplicateChild {
+                    parent: "a".to_string(),
+                    child: "b".to_string(),
+                },
+                Problem::NameMismatch {
+                    key: "e".to_string(),
+                    name: "renamed".to_string(),
+                },
+                Problem::MultipleParents {
+                    child: "e".to_string(),
+                    parents: strings(&["d", "f"]),
+                },
+                Problem::Cycle {
+                    groups: strings(&["a", "b", "c"]),
+                },
+                Problem::MissingWatched {
+                    group: "gone".to_string(),
+                },
+                Problem::DuplicateWatched {
+                    group: "a".to_string(),
+                },
+                Problem::DuplicateTaskId { id: 4 },
+                Problem::DuplicateNoteId { id: 1 },
+                Problem::TaskCounterBehind {
+                    last: 2,
+                    highest: 4
+                },
+                Problem::MissingBlocker { id: 3, blocker: 9 },
+                Problem::DependencyCycle { tasks: vec![1, 2] },
+            ]
+        );
+        assert_eq!(
+            problems[problems.len() - 1].to_string(),
+            "Tasks wait on each other: 1 -> 2 -> 1"
+        );
+        assert!(Project::new().check().is_empty());
+    }
+
+    #[test]
+    fn doctor_repair() {
+        let mut data = broken_project();
+        let repaired = data.repair();
+
+        assert_eq!(repaired.len(), 12);
+        assert!(data.check().is_empty());
+
+        // nothing was deleted, the cycle is broken above its lowest name
+        assert_eq!(data.groups.len(), 7);
+        assert_eq!(data.groups["a"].groups, strings(&["b"]));
+        assert_eq!(data.groups["c"].groups, Vec::<String>::new());
+        assert_eq!(data.get_group_path("c"), "a/b/c");
+        assert_eq!(data.groups["d"].groups, strings(&["e"]));
+        assert_eq!(data.groups["f"].groups, Vec::<String>::new());
+        assert_eq!(data.groups["e"].name, "e");
+        assert_eq!(data.active_groups, strings(&["a"]));
+
+        let f = &data.groups["f"];
+        assert_eq!(f.tasks.len(), 2);
+        assert_eq!(f.tasks[0].id, 4);
+        assert_eq!(f.tasks[1].id, 5);
+        assert_eq!(data.last_task_id, 5);
+        assert_eq!(f.notes[0].id, 1);
+        assert_eq!(f.notes[1].id, 2);
+        assert_eq!(data.last_note_id, 2);
+
+        // the link that closes the cycle goes, and so do links to missing tasks
+        let g = &data.groups["g"];
+        assert_eq!(g.tasks[0].blocked_by, vec![2]);
+        assert!(g.tasks[1].blocked_by.is_empty());
+        assert_eq!(g.tasks[2].blocked_by, vec![1]);
+    }
+
+    #[test]
+    fn doctor_load_refuses_broken_groups() {
+        let file_name = ".doctor-load-refuses-broken-groups.pmgr";
+        common::clean(file_name);
+        fs::write(file_name, BROKEN_PROJECT).unwrap();
+
+        match Store::new(file_name).load() {
+            Err(Error::Integrity { problems }) => assert_eq!(problems, 7),
+            Err(e) => panic!("Unexpected error: {}", e),
+            Ok(data) => panic!("Broken data was loaded: {:?}", data),
+        }
+        assert!(Store::new(file_name).load_unchecked().is_ok());
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn doctor_fix() {
+        let file_name = ".doctor-fix.pmgr";
+        common::clean(file_name);
+        fs::write(file_name, BROKEN_PROJECT).unwrap();
+
+        let Err(Error::Invalid(_)) = (commands::doctor::DoctorArgs { fix: false }).run(file_name)
+        else {
+            panic!("Problems were not reported");
+        };
+        // only looking never writes anything
+        assert_eq!(fs::read_to_string(file_name).unwrap(), BROKEN_PROJECT);
+
+        commands::doctor::DoctorArgs { fix: true }
+            .run(file_name)
+            .unwrap();
+
+        let Ok(data) = utils::get_data(file_name) else {
+            panic!("Failed to get data");
+        };
+        assert!(data.check().is_empty());
+
+        commands::doctor::DoctorArgs { fix: false }
+            .run(file_name)
+            .unwrap();
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn doctor_descendants_with_cycle() {
+        let data = broken_project();
+
+        assert_eq!(data.get_group_descendants("a"), strings(&["b", "c"]));
+        assert_eq!(data.get_group_descendants("c"), strings(&["a", "b"]));
+        assert!(data.get_group("ghost").is_err());
+    }
+}
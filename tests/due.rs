@@ -0,0 +1,121 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+    use pmgr::{
+        commands,
+        data::{Group, Project, Task, TaskState},
+        utils, Command,
+    };
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn parse_dates() {
+        // a wednesday
+        let today = date(2024, 10, 16);
+
+        assert_eq!(utils::parse_date("2024-12-01", today), Ok(date(2024, 12, 1)));
+        assert_eq!(utils::parse_date("today", today), Ok(today));
+        assert_eq!(utils::parse_date("Tomorrow", today), Ok(date(2024, 10, 17)));
+        assert_eq!(utils::parse_date("+3d", today), Ok(date(2024, 10, 19)));
+        assert_eq!(utils::parse_date("+2w", today), Ok(date(2024, 10, 30)));
+        assert_eq!(utils::parse_date("-7d", today), Ok(date(2024, 10, 9)));
+        assert_eq!(utils::parse_date("friday", today), Ok(date(2024, 10, 18)));
+        assert_eq!(utils::parse_date("mon", today), Ok(date(2024, 10, 21)));
+        assert_eq!(utils::parse_date("wednesday", today), Ok(date(2024, 10, 23)));
+
+        assert!(utils::parse_date("+3x", today).is_err());
+        assert!(utils::parse_date("2024-13-01", today).is_err());
+        assert!(utils::parse_date("someday", today).is_err());
+        assert!(utils::parse_date("+9999999999999999999w", today).is_err());
+        assert!(utils::parse_date("+é", today).is_err());
+        assert!(utils::parse_date("-", today).is_err());
+    }
+
+    #[test]
+    fn overdue_tasks() {
+        let today = date(2024, 10, 16);
+
+        let mut task = Task::new(1, "task1");
+        assert!(!task.is_overdue(today));
+
+        task.due = Some(date(2024, 10, 16));
+        assert!(!task.is_overdue(today));
+
+        task.due = Some(date(2024, 10, 15));
+        assert!(task.is_overdue(today));
+
+        task.state = TaskState::Complete;
+        assert!(!task.is_overdue(today));
+    }
+
+    #[test]
+    fn task_due() {
+        let file_name = ".task-due.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups_local!(
+            project,
+            group1 -> [],
+        );
+
+        add_tasks_local!(
+            project,
+            "group1",
+            1: TaskState::Incomplete -> "task1",
+            2: TaskState::Incomplete -> "task2",
+            3: TaskState::Incomplete -> "task3",
+        );
+
+        let tasks = &mut project.groups.get_mut("group1").unwrap().tasks;
+        tasks[0].due = Some(date(2024, 1, 31));
+        tasks[2].due = Some(date(2025, 2, 1));
+
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+
+        commands::add::AddTaskArgs {
+            group_name: "group1".to_string(),
+            text: vec!["task1".to_string()],
+            due: Some(date(2024, 1, 31)),
+            ..Default::default()
+        }
+        .run(file_name)
+        .unwrap();
+
+        add_tasks!(file_name, "group1", "task2", "task3",);
+
+        commands::task::TaskDueArgs {
+            group_name: "group1".to_string(),
+            date: "2025-02-01".to_string(),
+            ids: vec![2, 3],
+        }
+        .run(file_name)
+        .unwrap();
+
+        commands::task::TaskDueArgs {
+            group_name: "group1".to_string(),
+            date: "none".to_string(),
+            ids: vec![2],
+        }
+        .run(file_name)
+        .unwrap();
+
+        let Ok(mut data) = utils::get_data(file_name) else {
+            panic!("Failed to get data");
+        };
+
+        clear_timestamps!(data, project);
+        assert_eq!(data.groups, project.groups);
+
+        common::clean(file_name);
+    }
+}
@@ -0,0 +1,164 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{
+        commands,
+        data::{Group, Note, Priority, Project, Task, TaskState},
+        utils, Command, Error,
+    };
+    use std::env;
+
+    #[test]
+    fn edit_keeps_fields() {
+        let file_name = ".edit-keeps-fields.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups_local!(
+            project,
+            group1 -> [],
+        );
+        add_tasks_local!(
+            project,
+            "group1",
+            1: TaskState::Complete -> "task1",
+            2: TaskState::Incomplete -> "fixed task",
+            3: TaskState::Incomplete -> "task3",
+        );
+        add_notes_local!(
+            project,
+            "group1",
+            1: "fixed note",
+        );
+        let task = &mut project.groups.get_mut("group1").unwrap().tasks[1];
+        task.priority = Priority::High;
+        task.tags = vec!["bug".to_string()];
+        project.last_task_id = 3;
+        project.last_note_id = 1;
+
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+        add_tasks!(file_name, "group1", "task1", "tpyo task", "task3");
+        add_notes!(file_name, "group1", "tpyo note");
+        complete_tasks!(
+            file_name,
+            "group1",
+            1 -> TaskState::Complete,
+        );
+        commands::task::TaskPriorityArgs {
+            group_name: "group1".to_string(),
+            priority: Priority::High,
+            ids: vec![2],
+        }
+        .run(file_name)
+        .unwrap();
+        commands::tag::TagAddArgs {
+            kind: pmgr::data::ItemKind::Task,
+            group_name: "group1".to_string(),
+            id: 2,
+            tags: vec!["bug".to_string()],
+        }
+        .run(file_name)
+        .unwrap();
+
+        commands::edit::EditTaskArgs {
+            group_name: "group1".to_string(),
+            id: 2,
+            text: vec!["fixed".to_string(), "task".to_string()],
+            editor: false,
+        }
+        .run(file_name)
+        .unwrap();
+        commands::edit::EditNoteArgs {
+            group_name: "group1".to_string(),
+            id: 1,
+            text: vec!["fixed note".to_string()],
+            editor: false,
+        }
+        .run(file_name)
+        .unwrap();
+
+        let Ok(mut data) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+        let completed_at = data.get_group("group1").unwrap().tasks[0].completed_at;
+        clear_timestamps!(data, project);
+
+        common::clean(file_name);
+        assert!(completed_at.is_some());
+        assert_eq!(data, project);
+    }
+
+    #[test]
+    fn edit_errors() {
+        let file_name = ".edit-errors.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+        add_tasks!(file_name, "group1", "task1");
+
+        let missing_task = commands::edit::EditTaskArgs {
+            group_name: "group1".to_string(),
+            id: 2,
+            text: vec!["text".to_string()],
+            editor: false,
+        }
+        .run(file_name);
+        let missing_group = commands::edit::EditNoteArgs {
+            group_name: "group2".to_string(),
+            id: 1,
+            text: vec!["text".to_string()],
+            editor: false,
+        }
+        .run(file_name);
+        let no_text = commands::edit::EditTaskArgs {
+            group_name: "group1".to_string(),
+            id: 1,
+            ..Default::default()
+        }
+        .run(file_name);
+
+        common::clean(file_name);
+        assert!(matches!(missing_task, Err(Error::Missing(_))));
+        assert!(matches!(missing_group, Err(Error::Missing(_))));
+        assert!(matches!(no_text, Err(Error::Usage(_))));
+    }
+
+    #[test]
+    fn edit_in_editor() {
+        let file_name = ".edit-in-editor.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+        add_tasks!(file_name, "group1", "fix the tpyo");
+
+        env::set_var("VISUAL", "sed -i s/tpyo/typo/");
+        let result = commands::edit::EditTaskArgs {
+            group_name: "group1".to_string(),
+            id: 1,
+            editor: true,
+            ..Default::default()
+        }
+        .run(file_name);
+
+        let Ok(data) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+
+        common::clean(file_name);
+        assert!(result.is_ok());
+        assert_eq!(data.get_group("group1").unwrap().tasks[0].task, "fix the typo");
+    }
+}
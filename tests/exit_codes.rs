@@ -0,0 +1,140 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::{pmgr_code, project_dir};
+    use pmgr::error::{
+        EXIT_DATA, EXIT_INVALID, EXIT_IO, EXIT_MISSING, EXIT_NO_PROJECT, EXIT_USAGE,
+    };
+    use std::fs;
+
+    /// The name of the project file that the debug build of pmgr uses
+    const PROJECT_FILE: &str = ".debug.pmgr.json";
+
+    #[test]
+    fn exit_code_success() {
+        let dir = project_dir("pmgr-exit-success");
+
+        assert_eq!(pmgr_code(&dir, &["init"]), 0);
+        assert_eq!(pmgr_code(&dir, &["create", "group1"]), 0);
+        assert_eq!(pmgr_code(&dir, &["add", "task", "group1", "task1"]), 0);
+        assert_eq!(pmgr_code(&dir, &["task", "complete", "group1", "1"]), 0);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn exit_code_usage() {
+        let dir = project_dir("pmgr-exit-usage");
+
+        assert_eq!(pmgr_code(&dir, &["init"]), 0);
+        assert_eq!(pmgr_code(&dir, &["create", "group1"]), 0);
+        assert_eq!(pmgr_code(&dir, &["add", "task", "group1"]), EXIT_USAGE);
+        assert_eq!(pmgr_code(&dir, &["watch"]), EXIT_USAGE);
+        assert_eq!(pmgr_code(&dir, &["no-such-command"]), EXIT_USAGE);
+        // values too big for a date are refused instead of crashing
+        assert_eq!(
+            pmgr_code(&dir, &["agenda", "--days", "99999999999"]),
+            EXIT_USAGE
+        );
+        assert_eq!(
+            pmgr_code(&dir, &["list", "--due-before", "+9999999999999999999w"]),
+            EXIT_USAGE
+        );
+        assert_eq!(pmgr_code(&dir, &["list", "--due-before", "+é"]), EXIT_USAGE);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn exit_code_confirmation() {
+        let dir = project_dir("pmgr-exit-confirmation");
+
+        assert_eq!(pmgr_code(&dir, &["init"]), 0);
+        assert_eq!(pmgr_code(&dir, &["create", "group1"]), 0);
+        assert_eq!(pmgr_code(&dir, &["add", "task", "group1", "task1"]), 0);
+        assert_eq!(pmgr_code(&dir, &["watch", "group1"]), 0);
+
+        // there's no terminal to confirm on, so nothing is changed without --yes
+        assert_eq!(pmgr_code(&dir, &["delete", "group1"]), EXIT_USAGE);
+        assert_eq!(
+            pmgr_code(&dir, &["remove", "task", "group1", "1"]),
+            EXIT_USAGE
+        );
+        assert_eq!(pmgr_code(&dir, &["unwatch", "--all"]), EXIT_USAGE);
+        assert_eq!(pmgr_code(&dir, &["unwatch", "group1", "--yes"]), EXIT_USAGE);
+        assert_eq!(
+            pmgr_code(&dir, &["unwatch", "group1", "--dry-run"]),
+            EXIT_USAGE
+        );
+
+        assert_eq!(pmgr_code(&dir, &["delete", "group1", "--dry-run"]), 0);
+        assert_eq!(pmgr_code(&dir, &["list", "group1"]), 0);
+
+        assert_eq!(pmgr_code(&dir, &["unwatch", "--all", "--yes"]), 0);
+        assert_eq!(
+            pmgr_code(&dir, &["remove", "task", "group1", "1", "--yes"]),
+            0
+        );
+        assert_eq!(pmgr_code(&dir, &["delete", "group1", "-y"]), 0);
+        assert_eq!(pmgr_code(&dir, &["list", "group1"]), EXIT_MISSING);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn exit_code_no_project() {
+        let dir = project_dir("pmgr-exit-no-project");
+
+        assert_eq!(pmgr_code(&dir, &["list", "--all"]), EXIT_NO_PROJECT);
+        assert_eq!(pmgr_code(&dir, &["check"]), EXIT_NO_PROJECT);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn exit_code_missing() {
+        let dir = project_dir("pmgr-exit-missing");
+
+        assert_eq!(pmgr_code(&dir, &["init"]), 0);
+        assert_eq!(pmgr_code(&dir, &["create", "group1"]), 0);
+        assert_eq!(
+            pmgr_code(&dir, &["add", "task", "group2", "task1"]),
+            EXIT_MISSING
+        );
+        assert_eq!(
+            pmgr_code(&dir, &["task", "complete", "group1", "1"]),
+            EXIT_MISSING
+        );
+        assert_eq!(pmgr_code(&dir, &["delete", "group2"]), EXIT_MISSING);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn exit_code_invalid() {
+        let dir = project_dir("pmgr-exit-invalid");
+
+        assert_eq!(pmgr_code(&dir, &["init"]), 0);
+        assert_eq!(pmgr_code(&dir, &["create", "group1"]), 0);
+        assert_eq!(pmgr_code(&dir, &["create", "group1"]), EXIT_INVALID);
+        assert_eq!(pmgr_code(&dir, &["watch", "group1"]), 0);
+        assert_eq!(pmgr_code(&dir, &["watch", "group1"]), EXIT_INVALID);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn exit_code_bad_data() {
+        let dir = project_dir("pmgr-exit-bad-data");
+
+        fs::write(dir.join(PROJECT_FILE), "{\"information\": ").unwrap();
+        assert_eq!(pmgr_code(&dir, &["list", "--all"]), EXIT_DATA);
+
+        fs::remove_file(dir.join(PROJECT_FILE)).unwrap();
+        fs::create_dir(dir.join(PROJECT_FILE)).unwrap();
+        assert_eq!(pmgr_code(&dir, &["list", "--all"]), EXIT_IO);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
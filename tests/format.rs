@@ -0,0 +1,143 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::common::{pmgr_stdout, project_dir};
+    use pmgr::{
+        data::{Note, Priority, Task, TaskState},
+        output::{ItemRow, NoteView, TaskView},
+    };
+    use serde_json::{json, Value};
+    use std::fs;
+
+    #[test]
+    fn task_view() {
+        let today = chrono::NaiveDate::from_ymd_opt(2024, 10, 16).unwrap();
+        let mut task = Task::new(3, "fix login");
+        task.state = TaskState::Blocked {
+            reason: Some("waiting on review".to_string()),
+        };
+        task.priority = Priority::High;
+        task.due = today.pred_opt();
+        task.tags = vec!["bug".to_string(), "auth".to_string()];
+        task.created_at = None;
+
+        let view = TaskView::new("api", &task, today);
+        assert_eq!(
+            serde_json::to_value(&view).unwrap(),
+            json!({
+                "group": "api",
+                "id": 3,
+                "text": "fix login",
+                "state": "blocked",
+                "blocked_reason": "waiting on review",
+                "priority": "high",
+                "due": "2024-10-15",
+                "overdue": true,
+                "created_at": null,
+                "completed_at": null,
+                "tags": ["bug", "auth"],
+                "parent": null,
+                "subtasks": [],
+                "blocked_by": [],
+            })
+        );
+
+        let row = ItemRow::from(&view);
+        assert_eq!(row.kind, "task");
+        assert_eq!(row.tags, "bug auth");
+    }
+
+    #[test]
+    fn note_row() {
+        let note = Note {
+            tags: vec!["docs".to_string()],
+            ..Note::new(1, "note1")
+        };
+
+        let row = ItemRow::from(&NoteView::new("api", &note));
+        assert_eq!(row.kind, "note");
+        assert_eq!(row.state, None);
+        assert_eq!(row.priority, None);
+        assert_eq!(row.tags, "docs");
+    }
+
+    #[test]
+    fn structured_output() {
+        let dir = project_dir("pmgr-format");
+
+        pmgr_stdout(&dir, &["init"]);
+        pmgr_stdout(&dir, &["create", "api"]);
+        pmgr_stdout(&dir, &["add", "task", "api", "fix timeout", "--tag", "bug"]);
+        pmgr_stdout(&dir, &["add", "task", "api", "write docs"]);
+        pmgr_stdout(&dir, &["add", "note", "api", "note1"]);
+        pmgr_stdout(&dir, &["task", "complete", "api", "2"]);
+
+        let list: Value =
+            serde_json::from_str(&pmgr_stdout(&dir, &["list", "api", "--format", "json"])).unwrap();
+        assert_eq!(list["groups"][0]["name"], "api");
+        assert_eq!(list["groups"][0]["tasks"][0]["text"], "fix timeout");
+        assert_eq!(list["groups"][0]["tasks"][1]["state"], "complete");
+        assert_eq!(list["groups"][0]["notes"][0]["text"], "note1");
+
+        let progress: Value = serde_json::from_str(&pmgr_stdout(
+            &dir,
+            &["--format", "json", "task", "progress", "api"],
+        ))
+        .unwrap();
+        assert_eq!(progress["groups"][0]["percentage"], 50);
+        assert_eq!(progress["groups"][0]["total"], 2);
+
+        let read: Value =
+            serde_json::from_str(&pmgr_stdout(&dir, &["read", "--format", "json"])).unwrap();
+        assert_eq!(read["groups"]["api"]["tasks"][0]["task"], "fix timeout");
+
+        // filters keep every group, with only the items that match
+        let read: Value = serde_json::from_str(&pmgr_stdout(
+            &dir,
+            &["read", "--tag", "bug", "--format", "json"],
+        ))
+        .unwrap();
+        assert_eq!(read["groups"]["api"]["tasks"].as_array().unwrap().len(), 1);
+        assert_eq!(read["groups"]["api"]["notes"], json!([]));
+        let csv = pmgr_stdout(&dir, &["read", "--state", "complete", "--format", "csv"]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].starts_with("task,api,2,write docs,complete"));
+
+        let csv = pmgr_stdout(&dir, &["list", "api", "--format", "csv"]);
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[0].starts_with("kind,group,id,text,state"));
+        assert!(lines[1].starts_with("note,api,1,note1,"));
+
+        let tsv = pmgr_stdout(&dir, &["tags", "--format", "tsv"]);
+        assert_eq!(tsv, "tag\ttasks\tnotes\nbug\t1\t0\n");
+
+        pmgr_stdout(&dir, &["create", "auth", "api"]);
+        pmgr_stdout(&dir, &["add", "note", "auth", "login timeout is 5m"]);
+        let search: Value = serde_json::from_str(&pmgr_stdout(
+            &dir,
+            &["search", "timeout", "--format", "json"],
+        ))
+        .unwrap();
+        assert_eq!(search["hits"][0]["kind"], "task");
+        assert_eq!(search["hits"][0]["path"], "api");
+        assert_eq!(search["hits"][0]["tags"], json!(["bug"]));
+        assert_eq!(search["hits"][1]["kind"], "note");
+        assert_eq!(search["hits"][1]["path"], "api/auth");
+
+        let csv = pmgr_stdout(&dir, &["search", "timeout", "--format", "csv"]);
+        assert_eq!(
+            csv,
+            "kind,path,group,id,text,state\n\
+             task,api,api,1,fix timeout,incomplete\n\
+             note,api/auth,auth,2,login timeout is 5m,\n"
+        );
+
+        let yaml = pmgr_stdout(&dir, &["info", "--format", "yaml"]);
+        assert!(yaml.contains("title: null"));
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,240 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{
+        commands::{
+            self,
+            history::{RedoArgs, UndoArgs},
+        },
+        store::history::HISTORY_LIMIT,
+        utils, Command, Error, Store,
+    };
+    use std::fs;
+
+    fn summaries(file_name: &str) -> Vec<String> {
+        Store::new(file_name)
+            .load_history()
+            .unwrap()
+            .entries
+            .into_iter()
+            .map(|e| e.summary)
+            .collect()
+    }
+
+    #[test]
+    fn history_undo_redo_delete() {
+        let file_name = ".history-undo-redo-delete.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "api" -> ["auth", "users"],
+            "auth" -> ["tokens"],
+        );
+        add_tasks!(file_name, "tokens", "rotate keys");
+        let before = utils::get_data(file_name).unwrap();
+
+        commands::delete::DeleteArgs {
+            group_names: vec!["api".to_string()],
+            confirmation: commands::Confirmation {
+                yes: true,
+                dry_run: false,
+            },
+        }
+        .run(file_name)
+        .unwrap();
+        let after = utils::get_data(file_name).unwrap();
+        assert!(after.groups.is_empty());
+
+        // creating `auth` a second time failed, and failed commands are never recorded
+        assert_eq!(
+            summaries(file_name),
+            vec![
+                "create group api",
+                "create group auth in api",
+                "create group users in api",
+                "create group tokens in auth",
+                "add task \"rotate keys\" to group tokens",
+                "delete group api (+3 descendants)",
+            ]
+        );
+
+        UndoArgs.run(file_name).unwrap();
+        assert_eq!(utils::get_data(file_name).unwrap(), before);
+
+        RedoArgs.run(file_name).unwrap();
+        assert_eq!(utils::get_data(file_name).unwrap(), after);
+
+        let Err(Error::Invalid(_)) = RedoArgs.run(file_name) else {
+            panic!("Redid a change that wasn't undone");
+        };
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn history_change_drops_redo() {
+        let file_name = ".history-change-drops-redo.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        let Err(Error::Invalid(_)) = UndoArgs.run(file_name) else {
+            panic!("Undid a change on an empty history");
+        };
+
+        create_groups!(file_name, "group1" -> [],);
+        add_tasks!(file_name, "group1", "task1", "task2");
+
+        UndoArgs.run(file_name).unwrap();
+        UndoArgs.run(file_name).unwrap();
+        let history = Store::new(file_name).load_history().unwrap();
+        assert_eq!(history.entries.len(), 3);
+        assert_eq!(history.position, 1);
+
+        add_notes!(file_name, "group1", "note1");
+        assert_eq!(
+            summaries(file_name),
+            vec!["create group group1", "add note \"note1\" to group group1"]
+        );
+        let Err(Error::Invalid(_)) = RedoArgs.run(file_name) else {
+            panic!("Redid a change that was dropped");
+        };
+
+        let data = utils::get_data(file_name).unwrap();
+        assert!(data.groups["group1"].tasks.is_empty());
+        assert_eq!(data.groups["group1"].notes.len(), 1);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn history_outside_change() {
+        let file_name = ".history-outside-change.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+
+        let mut data = utils::get_data(file_name).unwrap();
+        data.information.title = Some("edited by hand".to_string());
+        Store::new(file_name).save(&data).unwrap();
+
+        let Err(Error::Invalid(_)) = UndoArgs.run(file_name) else {
+            panic!("Undo overwrote a change that isn't in the history");
+        };
+        assert_eq!(utils::get_data(file_name).unwrap(), data);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn history_unwritable() {
+        let file_name = ".history-unwritable.pmgr";
+        let history_dir = format!("{}.history", file_name);
+        fs::remove_dir_all(&history_dir).ok();
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        // a directory in the way of the history can't be replaced
+        fs::create_dir_all(format!("{}/entry", history_dir)).unwrap();
+        commands::create::CreateArgs {
+            group_name: "group1".to_string(),
+            parent_group: None,
+        }
+        .run(file_name)
+        .unwrap();
+        assert!(utils::get_data(file_name)
+            .unwrap()
+            .groups
+            .contains_key("group1"));
+
+        fs::remove_dir_all(&history_dir).unwrap();
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn history_undo_keeps_ids() {
+        let file_name = ".history-undo-keeps-ids.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+        add_tasks!(file_name, "group1", "task1");
+        add_notes!(file_name, "group1", "note1");
+
+        UndoArgs.run(file_name).unwrap();
+        UndoArgs.run(file_name).unwrap();
+        add_tasks!(file_name, "group1", "task2");
+        add_notes!(file_name, "group1", "note2");
+
+        // IDs that were given out before the undo aren't given out again
+        let data = utils::get_data(file_name).unwrap();
+        assert_eq!(data.groups["group1"].tasks[0].id, 2);
+        assert_eq!(data.groups["group1"].notes[0].id, 2);
+
+        UndoArgs.run(file_name).unwrap();
+        UndoArgs.run(file_name).unwrap();
+        RedoArgs.run(file_name).unwrap();
+        let data = utils::get_data(file_name).unwrap();
+        assert_eq!(data.groups["group1"].tasks[0].id, 2);
+        assert_eq!(data.last_task_id, 2);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn history_one_snapshot_per_change() {
+        let file_name = ".history-one-snapshot-per-change.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+        add_notes!(file_name, "group1", "note1", "note2", "note3");
+        let latest = utils::get_data(file_name).unwrap();
+
+        // each change keeps the project from before it, the latest one is kept once more
+        let history = Store::new(file_name).load_history().unwrap();
+        assert_eq!(history.entries.len(), 4);
+        assert_eq!(history.entries[3].snapshot.groups["group1"].notes.len(), 2);
+        assert_eq!(history.head.as_ref(), Some(&latest));
+
+        UndoArgs.run(file_name).unwrap();
+        UndoArgs.run(file_name).unwrap();
+        RedoArgs.run(file_name).unwrap();
+        RedoArgs.run(file_name).unwrap();
+        assert_eq!(utils::get_data(file_name).unwrap(), latest);
+
+        let history = Store::new(file_name).load_history().unwrap();
+        assert_eq!(history.position, 4);
+        assert_eq!(history.entries[2].snapshot.groups["group1"].notes.len(), 1);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn history_limit() {
+        let file_name = ".history-limit.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+        for i in 0..HISTORY_LIMIT {
+            commands::add::AddNoteArgs {
+                group_name: "group1".to_string(),
+                text: vec![format!("note{}", i)],
+                ..Default::default()
+            }
+            .run(file_name)
+            .unwrap();
+        }
+
+        let summaries = summaries(file_name);
+        assert_eq!(summaries.len(), HISTORY_LIMIT);
+        assert_eq!(summaries[0], "add note \"note0\" to group group1");
+
+        common::clean(file_name);
+    }
+}
@@ -0,0 +1,107 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{
+        commands,
+        data::{Group, Note, Project, Task, TaskState},
+        utils, Command,
+    };
+    use std::fs;
+
+    #[test]
+    fn ids_are_not_reused() {
+        let file_name = ".ids-not-reused.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups_local!(
+            project,
+            group1 -> [],
+        );
+
+        add_tasks_local!(
+            project,
+            "group1",
+            1: TaskState::Incomplete -> "task1",
+            3: TaskState::Complete -> "task3",
+            4: TaskState::Incomplete -> "task4",
+        );
+
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+
+        add_tasks!(file_name, "group1", "task1", "task2", "task3",);
+        remove_tasks!(file_name, "group1", 2);
+        add_tasks!(file_name, "group1", "task4",);
+
+        complete_tasks!(
+            file_name,
+            "group1",
+            3 -> TaskState::Complete,
+        );
+
+        let Ok(mut data) = utils::get_data(file_name) else {
+            panic!("Failed to get data");
+        };
+
+        clear_timestamps!(data, project);
+        assert_eq!(data.groups, project.groups);
+        assert_eq!(data.last_task_id, 4);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn legacy_ids_are_assigned() {
+        let file_name = ".legacy-ids.pmgr";
+        common::clean(file_name);
+
+        fs::write(
+            file_name,
+            r#"{
+                "information": {"title": null, "description": null, "repo": null},
+                "active_groups": [],
+                "groups": {
+                    "b": {
+                        "name": "b",
+                        "notes": [{"note": "note1"}],
+                        "tasks": [{"task": "task2", "state": "Incomplete"}],
+                        "groups": []
+                    },
+                    "a": {
+                        "name": "a",
+                        "notes": [],
+                        "tasks": [{"task": "task1", "state": "Complete"}],
+                        "groups": ["b"]
+                    }
+                }
+            }"#,
+        )
+        .unwrap();
+
+        create_groups_local!(
+            project,
+            a -> ["b"],
+            b -> [],
+        );
+
+        add_tasks_local!(project, "a", 1: TaskState::Complete -> "task1",);
+        add_tasks_local!(project, "b", 2: TaskState::Incomplete -> "task2",);
+        add_notes_local!(project, "b", 1: "note1",);
+
+        let Ok(mut data) = utils::get_data(file_name) else {
+            panic!("Failed to get data");
+        };
+
+        clear_timestamps!(data, project);
+        assert_eq!(data.groups, project.groups);
+        assert_eq!(data.last_task_id, 2);
+        assert_eq!(data.last_note_id, 1);
+
+        common::clean(file_name);
+    }
+}
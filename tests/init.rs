@@ -3,7 +3,6 @@ mod common;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use common;
     use pmgr::{commands, Command};
     use std::fs;
 
@@ -12,9 +11,9 @@ mod tests {
         let file_name = ".simple-init.pmgr";
         common::clean(file_name);
 
-        commands::init::InitArgs.run(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
 
-        if !fs::metadata(file_name).is_ok() {
+        if fs::metadata(file_name).is_err() {
             panic!("Project was not initalized");
         }
         common::clean(file_name);
@@ -0,0 +1,71 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{commands, utils, Command, Error, Store};
+    use std::{thread, time::Duration};
+
+    #[test]
+    fn parallel_writers() {
+        let file_name = ".parallel-writers.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+
+        let writers: Vec<_> = (0..4)
+            .map(|writer| {
+                thread::spawn(move || {
+                    for task in 0..10 {
+                        commands::add::AddTaskArgs {
+                            group_name: "group1".to_string(),
+                            text: vec![format!("task {}-{}", writer, task)],
+                            ..Default::default()
+                        }
+                        .run(file_name)
+                        .unwrap();
+                    }
+                })
+            })
+            .collect();
+
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let Ok(data) = utils::get_data(file_name) else {
+            panic!("Failed to get data");
+        };
+
+        let mut ids: Vec<usize> = data.groups["group1"].tasks.iter().map(|t| t.id).collect();
+        ids.sort();
+        assert_eq!(ids, (1..=40).collect::<Vec<usize>>());
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn locked_project() {
+        let file_name = ".locked-project.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        let store = Store::find(file_name).unwrap();
+        let lock = store.lock(Duration::ZERO).unwrap();
+
+        match store.lock(Duration::from_millis(50)) {
+            Err(Error::Locked { pid, .. }) => assert_eq!(pid, Some(std::process::id())),
+            Err(e) => panic!("Unexpected error: {}", e),
+            Ok(_) => panic!("Project was locked twice"),
+        }
+
+        drop(lock);
+        assert!(store.lock(Duration::ZERO).is_ok());
+
+        common::clean(file_name);
+    }
+}
@@ -0,0 +1,114 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{commands, data::SCHEMA_VERSION, utils, Command, Error, Store};
+    use std::fs;
+
+    const LEGACY_PROJECT: &str = r#"{
+        "information": {"title": null, "description": null, "repo": null},
+        "active_groups": [],
+        "groups": {
+            "group1": {
+                "name": "group1",
+                "notes": [],
+                "tasks": [{"task": "task1", "state": "Incomplete"}],
+                "groups": []
+            }
+        }
+    }"#;
+
+    #[test]
+    fn migrate_dry_run() {
+        let file_name = ".migrate-dry-run.pmgr";
+        common::clean(file_name);
+        fs::write(file_name, LEGACY_PROJECT).unwrap();
+
+        let (data, applied) = Store::new(file_name).load_migrated().unwrap();
+        assert_eq!(data.schema_version, SCHEMA_VERSION);
+        assert_eq!(applied.len(), 2);
+        assert_eq!(applied[0].changes.len(), 1);
+        assert!(applied[1].changes.is_empty());
+
+        commands::migrate::MigrateArgs { dry_run: true }
+            .run(file_name)
+            .unwrap();
+
+        // loading or a dry run never writes the upgraded data
+        assert_eq!(fs::read_to_string(file_name).unwrap(), LEGACY_PROJECT);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn migrate_writes_on_save() {
+        let file_name = ".migrate-writes-on-save.pmgr";
+        common::clean(file_name);
+        fs::write(file_name, LEGACY_PROJECT).unwrap();
+
+        commands::migrate::MigrateArgs { dry_run: false }
+            .run(file_name)
+            .unwrap();
+
+        let (data, applied) = Store::new(file_name).load_migrated().unwrap();
+        assert!(applied.is_empty());
+        assert_eq!(data.schema_version, SCHEMA_VERSION);
+        assert_eq!(data.groups["group1"].tasks[0].id, 1);
+
+        let Ok(data) = utils::get_data(file_name) else {
+            panic!("Failed to get data");
+        };
+        assert_eq!(data.last_task_id, 1);
+        assert!(Store::new(file_name)
+            .load_history()
+            .unwrap()
+            .entries
+            .is_empty());
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn migrate_from_version_1() {
+        let file_name = ".migrate-from-version-1.pmgr";
+        common::clean(file_name);
+
+        let version_1 = LEGACY_PROJECT
+            .replacen('{', "{\"schema_version\": 1,", 1)
+            .replace("\"task\": \"task1\"", "\"id\": 1, \"task\": \"task1\"");
+        fs::write(file_name, version_1).unwrap();
+
+        let (data, applied) = Store::new(file_name).load_migrated().unwrap();
+        assert_eq!(data.schema_version, SCHEMA_VERSION);
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].from, 1);
+
+        // fields added since version 1 get their defaults
+        let task = &data.groups["group1"].tasks[0];
+        assert_eq!(task.id, 1);
+        assert!(task.subtasks.is_empty());
+        assert!(task.blocked_by.is_empty());
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn migrate_newer_version() {
+        let file_name = ".migrate-newer-version.pmgr";
+        common::clean(file_name);
+
+        let newer = LEGACY_PROJECT.replacen('{', "{\"schema_version\": 9999,", 1);
+        fs::write(file_name, newer).unwrap();
+
+        match Store::new(file_name).load() {
+            Err(Error::VersionMismatch { found, supported }) => {
+                assert_eq!((found, supported), (9999, SCHEMA_VERSION))
+            }
+            Err(e) => panic!("Unexpected error: {}", e),
+            Ok(data) => panic!("Newer data was loaded: {:?}", data),
+        }
+
+        common::clean(file_name);
+    }
+}
@@ -0,0 +1,170 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Days, Utc};
+    use pmgr::{
+        commands,
+        data::{Note, Priority, Task, TaskState},
+        query::{self, StateFilter},
+        utils, Command, Error, Query,
+    };
+
+    fn task(id: usize, text: &str, state: TaskState, tags: &[&str]) -> Task {
+        Task {
+            state,
+            tags: tags.iter().map(|t| t.to_string()).collect(),
+            ..Task::new(id, text)
+        }
+    }
+
+    #[test]
+    fn empty_query_matches_everything() {
+        let query = Query::default();
+
+        assert!(!query.is_active());
+        assert!(query.matches_task(&Task::new(1, "task1")));
+        assert!(query.matches_note(&Note::new(1, "note1")));
+    }
+
+    #[test]
+    fn composed_filters() {
+        // incomplete tasks tagged bug whose text contains timeout
+        let query = Query {
+            states: vec![StateFilter::Incomplete],
+            tags: vec!["bug".to_string()],
+            text: Some("TIMEOUT".to_string()),
+            ..Default::default()
+        };
+
+        assert!(query.matches_task(&task(
+            1,
+            "fix timeout",
+            TaskState::Incomplete,
+            &["bug", "api"]
+        )));
+        assert!(!query.matches_task(&task(2, "fix timeout", TaskState::Complete, &["bug"])));
+        assert!(!query.matches_task(&task(3, "fix timeout", TaskState::Incomplete, &["api"])));
+        assert!(!query.matches_task(&task(4, "fix login", TaskState::Incomplete, &["bug"])));
+
+        // notes have no state, so a state filter leaves them out
+        let note = Note {
+            tags: vec!["bug".to_string()],
+            ..Note::new(1, "timeout seen")
+        };
+        assert!(!query.matches_note(&note));
+        assert!(Query {
+            states: vec![],
+            ..query
+        }
+        .matches_note(&note));
+    }
+
+    #[test]
+    fn state_filters() {
+        let blocked = TaskState::Blocked { reason: None };
+
+        assert!(StateFilter::Blocked.matches(&blocked));
+        assert!(StateFilter::Open.matches(&blocked));
+        assert!(StateFilter::Open.matches(&TaskState::InProgress));
+        assert!(!StateFilter::Open.matches(&TaskState::Cancelled));
+        assert!(StateFilter::Closed.matches(&TaskState::Complete));
+        assert!(StateFilter::Closed.matches(&TaskState::Cancelled));
+
+        let query = Query {
+            states: vec![StateFilter::Complete, StateFilter::Cancelled],
+            ..Default::default()
+        };
+        assert!(query.matches_task(&task(1, "task1", TaskState::Cancelled, &[])));
+        assert!(!query.matches_task(&task(2, "task2", TaskState::InProgress, &[])));
+    }
+
+    #[test]
+    fn task_fields() {
+        let today = utils::today();
+        let mut urgent = Task::new(1, "task1");
+        urgent.priority = Priority::Urgent;
+        urgent.due = today.checked_sub_days(Days::new(1));
+        let low = Task {
+            priority: Priority::Low,
+            ..Task::new(2, "task2")
+        };
+
+        let query = Query {
+            min_priority: Some(Priority::High),
+            ..Default::default()
+        };
+        assert!(query.matches_task(&urgent));
+        assert!(!query.matches_task(&low));
+
+        let query = Query {
+            overdue: true,
+            ..Default::default()
+        };
+        assert!(query.matches_task(&urgent));
+        assert!(!query.matches_task(&low));
+
+        let query = Query {
+            due_before: Some(today),
+            ..Default::default()
+        };
+        assert!(query.matches_task(&urgent));
+        assert!(!query.matches_task(&low));
+
+        let mut done = Task::new(3, "task3");
+        done.set_state(TaskState::Complete);
+        let query = Query {
+            completed_since: Some(utils::local_date(Utc::now())),
+            ..Default::default()
+        };
+        assert!(query.matches_task(&done));
+        assert!(!query.matches_task(&urgent));
+    }
+
+    #[test]
+    fn select_groups() {
+        let file_name = ".query-select-groups.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "api" -> ["auth"],
+            "web" -> [],
+        );
+        watch_groups!(file_name, true, "web");
+
+        let Ok(data) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+        common::clean(file_name);
+
+        let none = Query::default();
+        let tagged = Query {
+            tags: vec!["bug".to_string()],
+            ..Default::default()
+        };
+
+        assert_eq!(
+            query::select_groups(&data, Some("api"), false, &tagged).unwrap(),
+            vec!["api", "auth"]
+        );
+        assert_eq!(
+            query::select_groups(&data, None, false, &none).unwrap(),
+            vec!["web"]
+        );
+        assert_eq!(
+            query::select_groups(&data, None, false, &tagged).unwrap(),
+            vec!["api", "auth", "web"]
+        );
+        assert_eq!(
+            query::select_groups(&data, Some("api"), true, &none).unwrap(),
+            vec!["api", "auth", "web"]
+        );
+        assert!(matches!(
+            query::select_groups(&data, Some("missing"), false, &none),
+            Err(Error::Missing(_))
+        ));
+    }
+}
@@ -3,7 +3,6 @@ mod common;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use common;
     use pmgr::{
         commands,
         data::{Group, Note, Project, Task, TaskState},
@@ -14,7 +13,7 @@ mod tests {
     fn simple_remove() {
         let file_name = ".simple-remove.pmgr";
         common::clean(file_name);
-        commands::init::InitArgs.run(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
 
         create_groups_local!(
             project,
@@ -26,12 +25,12 @@ mod tests {
         add_tasks_local!(
             project,
             "group1",
-            TaskState::Incomplete -> "task1",
-            TaskState::Incomplete -> "task3",
-            TaskState::Incomplete -> "task5",
+            1: TaskState::Incomplete -> "task1",
+            3: TaskState::Incomplete -> "task3",
+            5: TaskState::Incomplete -> "task5",
         );
 
-        add_notes_local!(project, "group3", "note1", "note3", "note5",);
+        add_notes_local!(project, "group3", 1: "note1", 3: "note3", 5: "note5",);
 
         create_groups!(
             file_name,
@@ -47,10 +46,11 @@ mod tests {
         remove_tasks!(file_name, "group1", 2, 4);
         remove_notes!(file_name, "group3", 2, 4);
 
-        let Some(data) = utils::get_data(file_name) else {
+        let Ok(mut data) = utils::get_data(file_name) else {
             panic!("Failed to get data");
         };
 
+        clear_timestamps!(data, project);
         assert_eq!(data.groups, project.groups);
 
         common::clean(file_name);
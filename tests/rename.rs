@@ -0,0 +1,117 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{
+        commands::{self, rename::RenameArgs},
+        data::{Group, Note, Project, Task, TaskState},
+        utils, Command, Error,
+    };
+
+    fn rename(old_name: &str, new_name: &str) -> RenameArgs {
+        RenameArgs {
+            old_name: old_name.to_string(),
+            new_name: new_name.to_string(),
+        }
+    }
+
+    #[test]
+    fn rename_deep_group() {
+        let file_name = ".rename-deep-group.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups_local!(
+            project,
+            group1 -> ["group2"],
+            group2 -> ["group3"],
+            group3 -> ["renamed"],
+            renamed -> ["group5"],
+            group5 -> [],
+        );
+        add_tasks_local!(
+            project,
+            "renamed",
+            1: TaskState::Complete -> "task1",
+        );
+        add_notes_local!(
+            project,
+            "renamed",
+            1: "note1",
+        );
+        project.active_groups = vec![
+            "group3".to_string(),
+            "renamed".to_string(),
+            "group5".to_string(),
+        ];
+        project.last_task_id = 1;
+        project.last_note_id = 1;
+
+        create_groups!(
+            file_name,
+            "group1" -> ["group2"],
+            "group2" -> ["group3"],
+            "group3" -> ["group4"],
+            "group4" -> ["group5"],
+        );
+        add_tasks!(file_name, "group4", "task1");
+        add_notes!(file_name, "group4", "note1");
+        complete_tasks!(
+            file_name,
+            "group4",
+            1 -> TaskState::Complete,
+        );
+        watch_groups!(file_name, true, "group3");
+
+        rename("group4", "renamed").run(file_name).unwrap();
+
+        let Ok(mut data) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+        clear_timestamps!(data, project);
+
+        common::clean(file_name);
+        assert_eq!(data, project);
+        for (name, group) in &data.groups {
+            assert_eq!(name, &group.name);
+            for child in &group.groups {
+                assert!(data.groups.contains_key(child));
+            }
+        }
+        assert_eq!(
+            data.get_group_path("group5"),
+            "group1/group2/group3/renamed/group5"
+        );
+    }
+
+    #[test]
+    fn rename_errors() {
+        let file_name = ".rename-errors.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "group1" -> ["group2"],
+        );
+
+        let Ok(before) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+
+        let collision = rename("group1", "group2").run(file_name);
+        let missing = rename("group3", "group4").run(file_name);
+        let empty = rename("group1", " ").run(file_name);
+
+        let Ok(after) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+
+        common::clean(file_name);
+        assert!(matches!(collision, Err(Error::Invalid(_))));
+        assert!(matches!(missing, Err(Error::Missing(_))));
+        assert!(matches!(empty, Err(Error::Usage(_))));
+        assert_eq!(before, after);
+    }
+}
@@ -0,0 +1,94 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{
+        commands::{self, reparent::ReparentArgs},
+        data::{Group, Project},
+        utils, Command, Error,
+    };
+
+    fn reparent(group_name: &str, new_parent: Option<&str>) -> ReparentArgs {
+        ReparentArgs {
+            group_name: group_name.to_string(),
+            new_parent: new_parent.map(|p| p.to_string()),
+            root: new_parent.is_none(),
+        }
+    }
+
+    #[test]
+    fn reparent_subtree() {
+        let file_name = ".reparent-subtree.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups_local!(
+            project,
+            group1 -> ["group5"],
+            group2 -> [],
+            group3 -> ["group4", "group2"],
+            group4 -> [],
+            group5 -> ["group3"],
+        );
+        project.active_groups = vec!["group2".to_string(), "group3".to_string()];
+
+        create_groups!(
+            file_name,
+            "group1" -> ["group2"],
+            "group2" -> ["group3"],
+            "group3" -> ["group4"],
+            "group5" -> [],
+        );
+        watch_groups!(file_name, true, "group2");
+        watch_groups!(file_name, false, "group4");
+
+        reparent("group3", Some("group5")).run(file_name).unwrap();
+        reparent("group5", Some("group1")).run(file_name).unwrap();
+        reparent("group2", None).run(file_name).unwrap();
+        reparent("group2", Some("group3")).run(file_name).unwrap();
+
+        let Ok(data) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+
+        common::clean(file_name);
+        assert_eq!(data, project);
+        assert_eq!(data.get_group_path("group2"), "group1/group5/group3/group2");
+    }
+
+    #[test]
+    fn reparent_cycles() {
+        let file_name = ".reparent-cycles.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "group1" -> ["group2"],
+            "group2" -> ["group3"],
+        );
+
+        let Ok(before) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+
+        let itself = reparent("group1", Some("group1")).run(file_name);
+        let descendant = reparent("group1", Some("group3")).run(file_name);
+        let same_parent = reparent("group3", Some("group2")).run(file_name);
+        let already_root = reparent("group1", None).run(file_name);
+        let missing = reparent("group1", Some("group4")).run(file_name);
+
+        let Ok(after) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+
+        common::clean(file_name);
+        assert!(matches!(itself, Err(Error::Invalid(_))));
+        assert!(matches!(descendant, Err(Error::Invalid(_))));
+        assert!(matches!(same_parent, Err(Error::Invalid(_))));
+        assert!(matches!(already_root, Err(Error::Invalid(_))));
+        assert!(matches!(missing, Err(Error::Missing(_))));
+        assert_eq!(before, after);
+    }
+}
@@ -0,0 +1,78 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{commands, search::SearchArgs, utils, Command, Error};
+
+    fn search(pattern: &str) -> SearchArgs {
+        SearchArgs {
+            pattern: pattern.to_string(),
+            ignore_case: false,
+            regex: false,
+            watched: false,
+            group: None,
+        }
+    }
+
+    #[test]
+    fn group_paths() {
+        let file_name = ".search-group-paths.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "api" -> ["auth"],
+            "auth" -> ["tokens"],
+            "web" -> [],
+        );
+
+        let Ok(data) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+
+        common::clean(file_name);
+        assert_eq!(data.get_group_path("tokens"), "api/auth/tokens");
+        assert_eq!(data.get_group_path("auth"), "api/auth");
+        assert_eq!(data.get_group_path("web"), "web");
+        assert_eq!(data.get_group_parent("tokens"), Some("auth".to_string()));
+        assert_eq!(data.get_group_parent("api"), None);
+    }
+
+    #[test]
+    fn search_errors() {
+        let file_name = ".search-errors.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "api" -> [],
+        );
+        add_tasks!(file_name, "api", "fix timeout");
+
+        let found = search("timeout").run(file_name);
+        let invalid_regex = SearchArgs {
+            regex: true,
+            ..search("(")
+        }
+        .run(file_name);
+        let missing_group = SearchArgs {
+            group: Some("web".to_string()),
+            ..search("timeout")
+        }
+        .run(file_name);
+        let nothing_watched = SearchArgs {
+            watched: true,
+            ..search("timeout")
+        }
+        .run(file_name);
+
+        common::clean(file_name);
+        assert!(found.is_ok());
+        assert!(matches!(invalid_regex, Err(Error::Usage(_))));
+        assert!(matches!(missing_group, Err(Error::Missing(_))));
+        assert!(matches!(nothing_watched, Err(Error::Invalid(_))));
+    }
+}
@@ -0,0 +1,97 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{
+        data::{Group, Project},
+        Error, Store,
+    };
+    use std::fs;
+
+    #[test]
+    fn store_round_trip() {
+        let file_name = ".store-round-trip.pmgr";
+        common::clean(file_name);
+
+        create_groups_local!(
+            project,
+            group1 -> ["group2"],
+            group2 -> [],
+        );
+
+        let store = Store::new(file_name);
+        store.save(&project).unwrap();
+
+        let data = Store::find(file_name).unwrap().load().unwrap();
+        assert_eq!(data, project);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn store_not_found() {
+        let file_name = ".store-not-found.pmgr";
+        common::clean(file_name);
+
+        match Store::find(file_name) {
+            Err(Error::NotFound) => (),
+            Err(e) => panic!("Unexpected error: {}", e),
+            Ok(store) => panic!("Data shouldn't exist, exists at: {:?}", store.path()),
+        }
+    }
+
+    #[test]
+    fn store_parse_error() {
+        let file_name = ".store-parse-error.pmgr";
+        common::clean(file_name);
+
+        fs::write(file_name, "{\n  \"information\": {\n    \"title\": nope\n").unwrap();
+
+        match Store::new(file_name).load() {
+            Err(Error::Parse { line, column, .. }) => assert_eq!((line, column), (3, 15)),
+            Err(e) => panic!("Unexpected error: {}", e),
+            Ok(data) => panic!("Corrupt data was parsed: {:?}", data),
+        }
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn store_keeps_backup() {
+        let file_name = ".store-keeps-backup.pmgr";
+        common::clean(file_name);
+
+        create_groups_local!(
+            first,
+            group1 -> [],
+        );
+        create_groups_local!(
+            second,
+            group1 -> [],
+            group2 -> [],
+        );
+
+        let store = Store::new(file_name);
+        store.save(&first).unwrap();
+        assert!(fs::metadata(store.backup_path()).is_err());
+
+        store.save(&second).unwrap();
+        assert_eq!(store.load().unwrap(), second);
+
+        let backup = Store::new(store.backup_path()).load().unwrap();
+        assert_eq!(backup, first);
+
+        let leftovers: Vec<_> = fs::read_dir(".")
+            .unwrap()
+            .filter_map(|e| e.ok())
+            .filter(|e| {
+                let name = e.file_name().to_string_lossy().to_string();
+                name.starts_with(file_name) && name.ends_with(".tmp")
+            })
+            .collect();
+        assert!(leftovers.is_empty());
+
+        common::clean(file_name);
+    }
+}
@@ -0,0 +1,317 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{
+        commands,
+        data::{Priority, Task, TaskState},
+        utils, Command, Error, Query,
+    };
+
+    fn add_subtask(file_name: &str, group_name: &str, parent: usize, text: &str) {
+        commands::add::AddTaskArgs {
+            group_name: group_name.to_string(),
+            text: vec![text.to_string()],
+            parent: Some(parent),
+            ..Default::default()
+        }
+        .run(file_name)
+        .unwrap();
+    }
+
+    fn state(file_name: &str, group_name: &str, id: usize) -> TaskState {
+        let data = utils::get_data(file_name).unwrap();
+        data.groups[group_name].find_task(id).unwrap().state.clone()
+    }
+
+    #[test]
+    fn subtask_add() {
+        let file_name = ".subtask-add.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+        add_tasks!(file_name, "group1", "task1", "task2");
+        add_subtask(file_name, "group1", 1, "step1");
+        add_subtask(file_name, "group1", 3, "step2");
+
+        let data = utils::get_data(file_name).unwrap();
+        let group = &data.groups["group1"];
+        assert_eq!(group.tasks.len(), 2);
+        assert_eq!(group.tasks[0].subtasks[0].id, 3);
+        assert_eq!(group.tasks[0].subtasks[0].subtasks[0].id, 4);
+        assert_eq!(
+            group.all_tasks().iter().map(|t| t.id).collect::<Vec<_>>(),
+            vec![1, 3, 4, 2]
+        );
+        assert_eq!(group.parent_task(4), Some(3));
+        assert_eq!(group.parent_task(1), None);
+
+        let Err(Error::Missing(_)) = commands::add::AddTaskArgs {
+            group_name: "group1".to_string(),
+            text: vec!["step3".to_string()],
+            parent: Some(9),
+            ..Default::default()
+        }
+        .run(file_name) else {
+            panic!("Added a sub-task to a task that doesn't exist");
+        };
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn subtask_cascade() {
+        let file_name = ".subtask-cascade.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+        add_tasks!(file_name, "group1", "task1");
+        add_subtask(file_name, "group1", 1, "step1");
+        add_subtask(file_name, "group1", 1, "step2");
+        add_subtask(file_name, "group1", 2, "step3");
+
+        commands::task::TaskCancelArgs {
+            group_name: "group1".to_string(),
+            ids: vec![3],
+        }
+        .run(file_name)
+        .unwrap();
+
+        // without cascading, only the parent changes
+        complete_tasks!(file_name, "group1", 1 -> TaskState::Complete,);
+        assert_eq!(state(file_name, "group1", 2), TaskState::Incomplete);
+        complete_tasks!(file_name, "group1", 1 -> TaskState::Incomplete,);
+
+        commands::task::TaskCompleteArgs {
+            group_name: "group1".to_string(),
+            ids: vec![1],
+            cascade: true,
+            force: false,
+        }
+        .run(file_name)
+        .unwrap();
+
+        assert_eq!(state(file_name, "group1", 1), TaskState::Complete);
+        assert_eq!(state(file_name, "group1", 2), TaskState::Complete);
+        assert_eq!(state(file_name, "group1", 3), TaskState::Cancelled);
+        assert_eq!(state(file_name, "group1", 4), TaskState::Complete);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn subtask_auto_complete() {
+        let file_name = ".subtask-auto-complete.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+        add_tasks!(file_name, "group1", "task1");
+        add_subtask(file_name, "group1", 1, "step1");
+        add_subtask(file_name, "group1", 2, "step2");
+        add_subtask(file_name, "group1", 1, "step3");
+
+        complete_tasks!(file_name, "group1", 3 -> TaskState::Complete,);
+        assert_eq!(state(file_name, "group1", 2), TaskState::Complete);
+        assert_eq!(state(file_name, "group1", 1), TaskState::Incomplete);
+
+        // a cancelled sub-task is done, but cancelling alone doesn't complete anything
+        commands::task::TaskCancelArgs {
+            group_name: "group1".to_string(),
+            ids: vec![4],
+        }
+        .run(file_name)
+        .unwrap();
+        assert_eq!(state(file_name, "group1", 1), TaskState::Complete);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn subtask_reopen_parent() {
+        let file_name = ".subtask-reopen-parent.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+        add_tasks!(file_name, "group1", "parent");
+        add_subtask(file_name, "group1", 1, "child");
+        complete_tasks!(file_name, "group1", 2 -> TaskState::Complete,);
+        assert_eq!(state(file_name, "group1", 1), TaskState::Complete);
+
+        // the parent keeps the state it was given, even though its sub-tasks are done
+        complete_tasks!(file_name, "group1", 1 -> TaskState::Incomplete,);
+        assert_eq!(state(file_name, "group1", 1), TaskState::Incomplete);
+        commands::task::TaskStartArgs {
+            group_name: "group1".to_string(),
+            ids: vec![1],
+        }
+        .run(file_name)
+        .unwrap();
+        assert_eq!(state(file_name, "group1", 1), TaskState::InProgress);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn subtask_details_keep_parent() {
+        let file_name = ".subtask-details-keep-parent.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+        add_tasks!(file_name, "group1", "task1", "task2");
+        add_subtask(file_name, "group1", 1, "step1");
+        add_subtask(file_name, "group1", 1, "step2");
+        add_subtask(file_name, "group1", 2, "step3");
+        complete_tasks!(file_name, "group1", 1 -> TaskState::Complete,);
+        complete_tasks!(file_name, "group1", 5 -> TaskState::Complete,);
+        complete_tasks!(file_name, "group1", 2 -> TaskState::Incomplete,);
+
+        // only state changes complete or reopen parents, other details leave them as they are
+        commands::task::TaskPriorityArgs {
+            group_name: "group1".to_string(),
+            priority: Priority::High,
+            ids: vec![3, 5],
+        }
+        .run(file_name)
+        .unwrap();
+        commands::task::TaskDueArgs {
+            group_name: "group1".to_string(),
+            date: "+3d".to_string(),
+            ids: vec![4, 5],
+        }
+        .run(file_name)
+        .unwrap();
+
+        assert_eq!(state(file_name, "group1", 1), TaskState::Complete);
+        assert_eq!(state(file_name, "group1", 3), TaskState::Incomplete);
+        assert_eq!(state(file_name, "group1", 2), TaskState::Incomplete);
+        assert_eq!(state(file_name, "group1", 5), TaskState::Complete);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn subtask_reopen_child() {
+        let file_name = ".subtask-reopen-child.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [],);
+        add_tasks!(file_name, "group1", "task1");
+        add_subtask(file_name, "group1", 1, "step1");
+        add_subtask(file_name, "group1", 2, "step2");
+        complete_tasks!(file_name, "group1", 3 -> TaskState::Complete,);
+        assert_eq!(state(file_name, "group1", 1), TaskState::Complete);
+
+        complete_tasks!(file_name, "group1", 3 -> TaskState::Incomplete,);
+        assert_eq!(state(file_name, "group1", 2), TaskState::Incomplete);
+        assert_eq!(state(file_name, "group1", 1), TaskState::Incomplete);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn subtask_progress() {
+        let mut task = Task::new(1, "task1");
+        assert_eq!(task.progress(), 0.0);
+
+        task.subtasks = vec![
+            Task::new(2, "step1"),
+            Task::new(3, "step2"),
+            Task::new(4, "step3"),
+        ];
+        task.subtasks[0].set_state(TaskState::Complete);
+        task.subtasks[1].subtasks = vec![Task::new(5, "step4"), Task::new(6, "step5")];
+        task.subtasks[1].subtasks[0].set_state(TaskState::Complete);
+        task.subtasks[2].set_state(TaskState::Cancelled);
+
+        // (1 + 0.5) out of the 2 sub-tasks that aren't cancelled
+        assert_eq!(task.progress(), 0.75);
+        assert_eq!(task.subtask_counts(), (1, 2));
+
+        task.set_state(TaskState::Complete);
+        assert_eq!(task.progress(), 1.0);
+    }
+
+    #[test]
+    fn subtask_query_prune() {
+        let mut task = Task::new(1, "task1");
+        task.subtasks = vec![Task::new(2, "fix login"), Task::new(3, "write docs")];
+
+        let query = Query {
+            text: Some("login".to_string()),
+            ..Default::default()
+        };
+        let pruned = query.prune(&task).unwrap();
+        assert_eq!(pruned.id, 1);
+        assert_eq!(pruned.subtasks.len(), 1);
+        assert_eq!(pruned.subtasks[0].id, 2);
+
+        let query = Query {
+            text: Some("deploy".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(query.prune(&task), None);
+    }
+
+    #[test]
+    fn subtask_remove_and_copy() {
+        let file_name = ".subtask-remove-and-copy.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(file_name, "group1" -> [], "group2" -> [],);
+        add_tasks!(file_name, "group1", "task1", "task2");
+        add_subtask(file_name, "group1", 1, "step1");
+        add_subtask(file_name, "group1", 3, "step2");
+
+        commands::transfer::CopyTaskArgs {
+            from_group: "group1".to_string(),
+            ids: vec![1],
+            to_group: "group2".to_string(),
+            position: None,
+        }
+        .run(file_name)
+        .unwrap();
+
+        let data = utils::get_data(file_name).unwrap();
+        assert_eq!(
+            data.groups["group2"]
+                .all_tasks()
+                .iter()
+                .map(|t| t.id)
+                .collect::<Vec<_>>(),
+            vec![5, 6, 7]
+        );
+        assert!(data.check().is_empty());
+
+        let Err(Error::Invalid(_)) = commands::transfer::MoveTaskArgs {
+            from_group: "group1".to_string(),
+            ids: vec![3],
+            to_group: "group2".to_string(),
+            position: None,
+        }
+        .run(file_name) else {
+            panic!("Moved a sub-task away from its parent");
+        };
+
+        remove_tasks!(file_name, "group1", 3);
+        let data = utils::get_data(file_name).unwrap();
+        assert_eq!(
+            data.groups["group1"]
+                .all_tasks()
+                .iter()
+                .map(|t| t.id)
+                .collect::<Vec<_>>(),
+            vec![1, 2]
+        );
+        assert!(data.check().is_empty());
+
+        common::clean(file_name);
+    }
+}
@@ -0,0 +1,159 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{
+        commands,
+        data::{Group, ItemKind, Note, Project, Task, TaskState},
+        utils, Command, Error,
+    };
+
+    #[test]
+    fn parse_tags() {
+        assert_eq!(utils::parse_tag("bug"), Ok("bug".to_string()));
+        assert_eq!(utils::parse_tag("#auth"), Ok("auth".to_string()));
+
+        assert!(utils::parse_tag("").is_err());
+        assert!(utils::parse_tag("#").is_err());
+        assert!(utils::parse_tag("two words").is_err());
+    }
+
+    #[test]
+    fn add_with_tags() {
+        let file_name = ".tag-add.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups_local!(
+            project,
+            group1 -> [],
+        );
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+
+        commands::add::AddTaskArgs {
+            group_name: "group1".to_string(),
+            text: vec!["fix login".to_string()],
+            tags: vec!["bug".to_string(), "auth".to_string(), "bug".to_string()],
+            ..Default::default()
+        }
+        .run(file_name)
+        .unwrap();
+        commands::add::AddNoteArgs {
+            group_name: "group1".to_string(),
+            text: vec!["note1".to_string()],
+            tags: vec!["auth".to_string()],
+        }
+        .run(file_name)
+        .unwrap();
+
+        project.groups.get_mut("group1").unwrap().tasks.push(Task {
+            tags: vec!["bug".to_string(), "auth".to_string()],
+            ..Task::new(1, "fix login")
+        });
+        project.groups.get_mut("group1").unwrap().notes.push(Note {
+            tags: vec!["auth".to_string()],
+            ..Note::new(1, "note1")
+        });
+        project.last_task_id = 1;
+        project.last_note_id = 1;
+
+        let Ok(mut data) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+        clear_timestamps!(data, project);
+
+        common::clean(file_name);
+        assert_eq!(data, project);
+    }
+
+    #[test]
+    fn tag_add_remove() {
+        let file_name = ".tag-add-remove.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+        add_tasks!(file_name, "group1", "task1");
+        add_notes!(file_name, "group1", "note1");
+
+        commands::tag::TagAddArgs {
+            kind: ItemKind::Task,
+            group_name: "group1".to_string(),
+            id: 1,
+            tags: vec!["bug".to_string(), "auth".to_string()],
+        }
+        .run(file_name)
+        .unwrap();
+        commands::tag::TagAddArgs {
+            kind: ItemKind::Task,
+            group_name: "group1".to_string(),
+            id: 1,
+            tags: vec!["auth".to_string(), "ui".to_string()],
+        }
+        .run(file_name)
+        .unwrap();
+        commands::tag::TagAddArgs {
+            kind: ItemKind::Note,
+            group_name: "group1".to_string(),
+            id: 1,
+            tags: vec!["docs".to_string()],
+        }
+        .run(file_name)
+        .unwrap();
+        commands::tag::TagRemoveArgs {
+            kind: ItemKind::Task,
+            group_name: "group1".to_string(),
+            id: 1,
+            tags: vec!["bug".to_string(), "missing".to_string()],
+        }
+        .run(file_name)
+        .unwrap();
+
+        let missing_item = commands::tag::TagAddArgs {
+            kind: ItemKind::Note,
+            group_name: "group1".to_string(),
+            id: 2,
+            tags: vec!["docs".to_string()],
+        }
+        .run(file_name);
+        let missing_group = commands::tag::TagAddArgs {
+            kind: ItemKind::Task,
+            group_name: "group2".to_string(),
+            id: 1,
+            tags: vec!["docs".to_string()],
+        }
+        .run(file_name);
+
+        let Ok(data) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+        let group = data.get_group("group1").unwrap();
+
+        common::clean(file_name);
+        assert_eq!(group.tasks[0].tags, vec!["auth", "ui"]);
+        assert_eq!(group.notes[0].tags, vec!["docs"]);
+        assert!(matches!(missing_item, Err(Error::Missing(_))));
+        assert!(matches!(missing_group, Err(Error::Missing(_))));
+    }
+
+    #[test]
+    fn tags_are_optional_in_old_files() {
+        let task: Task =
+            serde_json::from_str(r#"{"id":1,"task":"task1","state":"Incomplete"}"#).unwrap();
+        assert_eq!(task.state, TaskState::Incomplete);
+        assert!(task.tags.is_empty());
+
+        let group: Group = serde_json::from_str(
+            r#"{"name":"g","notes":[{"id":1,"note":"n"}],"tasks":[],"groups":[]}"#,
+        )
+        .unwrap();
+        assert!(group.notes[0].tags.is_empty());
+    }
+}
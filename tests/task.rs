@@ -3,18 +3,19 @@ mod common;
 #[cfg(test)]
 mod tests {
     use super::*;
-    use common;
+    use chrono::Utc;
     use pmgr::{
         commands,
-        data::{Group, Project, Task, TaskState},
-        utils, Command,
+        data::{Group, Priority, Project, Task, TaskState},
+        task::SortKey,
+        utils, Command, Error,
     };
 
     #[test]
     fn task_complete() {
         let file_name = ".task-complete.pmgr";
         common::clean(file_name);
-        commands::init::InitArgs.run(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
 
         create_groups_local!(
             project,
@@ -26,11 +27,11 @@ mod tests {
         add_tasks_local!(
             project,
             "group3",
-            TaskState::Complete -> "task1",
-            TaskState::Incomplete -> "task2",
-            TaskState::Complete -> "task3",
-            TaskState::Incomplete -> "task4",
-            TaskState::Complete -> "task5",
+            1: TaskState::Complete -> "task1",
+            2: TaskState::Incomplete -> "task2",
+            3: TaskState::Complete -> "task3",
+            4: TaskState::Incomplete -> "task4",
+            5: TaskState::Complete -> "task5",
         );
 
         create_groups!(
@@ -50,10 +51,11 @@ mod tests {
             5 -> TaskState::Complete,
         );
 
-        let Some(data) = utils::get_data(file_name) else {
+        let Ok(mut data) = utils::get_data(file_name) else {
             panic!("Failed to get data");
         };
 
+        clear_timestamps!(data, project);
         assert_eq!(data.groups, project.groups);
 
         common::clean(file_name);
@@ -63,7 +65,7 @@ mod tests {
     fn task_undo() {
         let file_name = ".task-undo.pmgr";
         common::clean(file_name);
-        commands::init::InitArgs.run(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
 
         create_groups_local!(
             project,
@@ -75,11 +77,11 @@ mod tests {
         add_tasks_local!(
             project,
             "group3",
-            TaskState::Incomplete -> "task1",
-            TaskState::Incomplete -> "task2",
-            TaskState::Incomplete -> "task3",
-            TaskState::Incomplete -> "task4",
-            TaskState::Incomplete -> "task5",
+            1: TaskState::Incomplete -> "task1",
+            2: TaskState::Incomplete -> "task2",
+            3: TaskState::Incomplete -> "task3",
+            4: TaskState::Incomplete -> "task4",
+            5: TaskState::Incomplete -> "task5",
         );
 
         create_groups!(
@@ -107,12 +109,328 @@ mod tests {
             5 -> TaskState::Incomplete,
         );
 
-        let Some(data) = utils::get_data(file_name) else {
+        let Ok(mut data) = utils::get_data(file_name) else {
             panic!("Failed to get data");
         };
 
+        clear_timestamps!(data, project);
         assert_eq!(data.groups, project.groups);
 
         common::clean(file_name);
     }
+
+    #[test]
+    fn task_priority() {
+        let file_name = ".task-priority.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups_local!(
+            project,
+            group1 -> [],
+        );
+
+        add_tasks_local!(
+            project,
+            "group1",
+            1: TaskState::Incomplete -> "task1",
+            2: TaskState::Incomplete -> "task2",
+            3: TaskState::Incomplete -> "task3",
+        );
+
+        let tasks = &mut project.groups.get_mut("group1").unwrap().tasks;
+        tasks[0].priority = Priority::Urgent;
+        tasks[1].priority = Priority::Low;
+        tasks[2].priority = Priority::Low;
+
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+
+        commands::add::AddTaskArgs {
+            group_name: "group1".to_string(),
+            text: vec!["task1".to_string()],
+            priority: Priority::High,
+            ..Default::default()
+        }
+        .run(file_name)
+        .unwrap();
+
+        add_tasks!(file_name, "group1", "task2", "task3",);
+
+        commands::task::TaskPriorityArgs {
+            group_name: "group1".to_string(),
+            priority: Priority::Urgent,
+            ids: vec![1],
+        }
+        .run(file_name)
+        .unwrap();
+
+        commands::task::TaskPriorityArgs {
+            group_name: "group1".to_string(),
+            priority: Priority::Low,
+            ids: vec![2, 3],
+        }
+        .run(file_name)
+        .unwrap();
+
+        let Ok(mut data) = utils::get_data(file_name) else {
+            panic!("Failed to get data");
+        };
+
+        clear_timestamps!(data, project);
+        assert_eq!(data.groups, project.groups);
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn task_states() {
+        let file_name = ".task-states.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups_local!(
+            project,
+            group1 -> [],
+        );
+
+        add_tasks_local!(
+            project,
+            "group1",
+            1: TaskState::InProgress -> "task1",
+            2: TaskState::Cancelled -> "task2",
+            3: TaskState::Incomplete -> "task3",
+            4: TaskState::Cancelled -> "task4",
+        );
+
+        project.groups.get_mut("group1").unwrap().tasks[2].state = TaskState::Blocked {
+            reason: Some("waiting on review".to_string()),
+        };
+
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+
+        add_tasks!(file_name, "group1", "task1", "task2", "task3", "task4",);
+
+        commands::task::TaskStartArgs {
+            group_name: "group1".to_string(),
+            ids: vec![1],
+        }
+        .run(file_name)
+        .unwrap();
+
+        commands::task::TaskBlockArgs {
+            group_name: "group1".to_string(),
+            ids: vec![3],
+            reason: Some("waiting on review".to_string()),
+        }
+        .run(file_name)
+        .unwrap();
+
+        commands::task::TaskCancelArgs {
+            group_name: "group1".to_string(),
+            ids: vec![2, 4],
+        }
+        .run(file_name)
+        .unwrap();
+
+        let Ok(mut data) = utils::get_data(file_name) else {
+            panic!("Failed to get data");
+        };
+
+        clear_timestamps!(data, project);
+        assert_eq!(data.groups, project.groups);
+        assert!(data.groups["group1"].tasks[1].state.is_closed());
+        assert!(!data.groups["group1"].tasks[2].state.is_closed());
+
+        common::clean(file_name);
+    }
+
+    #[test]
+    fn task_timestamps() {
+        let file_name = ".task-timestamps.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+
+        let before = Utc::now();
+        add_tasks!(file_name, "group1", "task1", "task2",);
+        add_notes!(file_name, "group1", "note1",);
+
+        complete_tasks!(
+            file_name,
+            "group1",
+            1 -> TaskState::Complete,
+            2 -> TaskState::Complete,
+        );
+
+        let Ok(data) = utils::get_data(file_name) else {
+            panic!("Failed to get data");
+        };
+
+        let group = &data.groups["group1"];
+        assert!(group.notes[0].created_at.is_some_and(|t| t >= before));
+        for task in &group.tasks {
+            let (Some(created_at), Some(completed_at)) = (task.created_at, task.completed_at)
+            else {
+                panic!("Task is missing timestamps: {:?}", task);
+            };
+            assert!(before <= created_at && created_at <= completed_at);
+        }
+
+        // completing an already complete task keeps the original completion time
+        complete_tasks!(
+            file_name,
+            "group1",
+            1 -> TaskState::Complete,
+        );
+        complete_tasks!(
+            file_name,
+            "group1",
+            2 -> TaskState::Incomplete,
+        );
+
+        let Ok(updated) = utils::get_data(file_name) else {
+            panic!("Failed to get data");
+        };
+
+        let tasks = &updated.groups["group1"].tasks;
+        assert_eq!(tasks[0].completed_at, group.tasks[0].completed_at);
+        assert_eq!(tasks[1].completed_at, None);
+        assert_eq!(tasks[1].created_at, group.tasks[1].created_at);
+
+        common::clean(file_name);
+    }
+
+    fn task_ids(file_name: &str) -> Vec<usize> {
+        let Ok(data) = utils::get_data(file_name) else {
+            panic!("Failed to get data");
+        };
+        data.get_group("group1").unwrap()
+            .tasks
+            .iter()
+            .map(|t| t.id)
+            .collect()
+    }
+
+    #[test]
+    fn task_reorder() {
+        let file_name = ".task-reorder.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+        add_tasks!(file_name, "group1", "task1", "task2", "task3", "task4");
+
+        let reorder = |id: usize| commands::task::TaskReorderArgs {
+            group_name: "group1".to_string(),
+            id,
+            ..Default::default()
+        };
+
+        commands::task::TaskReorderArgs {
+            to: Some(2),
+            ..reorder(4)
+        }
+        .run(file_name)
+        .unwrap();
+        assert_eq!(task_ids(file_name), vec![1, 4, 2, 3]);
+
+        commands::task::TaskReorderArgs {
+            up: true,
+            ..reorder(2)
+        }
+        .run(file_name)
+        .unwrap();
+        assert_eq!(task_ids(file_name), vec![1, 2, 4, 3]);
+
+        commands::task::TaskReorderArgs {
+            down: true,
+            ..reorder(3)
+        }
+        .run(file_name)
+        .unwrap();
+        assert_eq!(task_ids(file_name), vec![1, 2, 4, 3]);
+
+        commands::task::TaskReorderArgs {
+            top: true,
+            ..reorder(3)
+        }
+        .run(file_name)
+        .unwrap();
+        assert_eq!(task_ids(file_name), vec![3, 1, 2, 4]);
+
+        commands::task::TaskReorderArgs {
+            bottom: true,
+            ..reorder(3)
+        }
+        .run(file_name)
+        .unwrap();
+        assert_eq!(task_ids(file_name), vec![1, 2, 4, 3]);
+
+        let out_of_range = commands::task::TaskReorderArgs {
+            to: Some(5),
+            ..reorder(1)
+        }
+        .run(file_name);
+        let missing = commands::task::TaskReorderArgs {
+            top: true,
+            ..reorder(9)
+        }
+        .run(file_name);
+
+        common::clean(file_name);
+        assert!(matches!(out_of_range, Err(Error::Usage(_))));
+        assert!(matches!(missing, Err(Error::Missing(_))));
+    }
+
+    #[test]
+    fn task_sort() {
+        let file_name = ".task-sort.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "group1" -> [],
+        );
+        add_tasks!(file_name, "group1", "b task", "C task", "a task", "d task");
+        complete_tasks!(
+            file_name,
+            "group1",
+            1 -> TaskState::Complete,
+        );
+        commands::task::TaskStartArgs {
+            group_name: "group1".to_string(),
+            ids: vec![4],
+        }
+        .run(file_name)
+        .unwrap();
+
+        let sort = |by: SortKey| {
+            commands::task::TaskSortArgs {
+                group_name: "group1".to_string(),
+                by,
+            }
+            .run(file_name)
+            .unwrap();
+            task_ids(file_name)
+        };
+
+        assert_eq!(sort(SortKey::Text), vec![3, 1, 2, 4]);
+        assert_eq!(sort(SortKey::State), vec![4, 3, 2, 1]);
+
+        common::clean(file_name);
+    }
 }
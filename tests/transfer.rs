@@ -0,0 +1,186 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pmgr::{
+        commands::{
+            self,
+            transfer::{CopyNoteArgs, CopyTaskArgs, MoveNoteArgs, MoveTaskArgs},
+        },
+        data::{Group, Note, Project, Task, TaskState},
+        utils, Command, Error,
+    };
+
+    #[test]
+    fn move_tasks() {
+        let file_name = ".transfer-move-tasks.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups_local!(
+            project,
+            backlog -> [],
+            sprint -> [],
+        );
+        add_tasks_local!(
+            project,
+            "backlog",
+            2: TaskState::Incomplete -> "task2",
+        );
+        add_tasks_local!(
+            project,
+            "sprint",
+            1: TaskState::Complete -> "task1",
+            3: TaskState::Incomplete -> "task3",
+            4: TaskState::Incomplete -> "task4",
+        );
+        add_notes_local!(
+            project,
+            "sprint",
+            1: "note1",
+        );
+        project.last_task_id = 4;
+        project.last_note_id = 1;
+
+        create_groups!(
+            file_name,
+            "backlog" -> [],
+            "sprint" -> [],
+        );
+        add_tasks!(file_name, "backlog", "task1", "task2", "task3");
+        add_tasks!(file_name, "sprint", "task4");
+        add_notes!(file_name, "backlog", "note1");
+        complete_tasks!(
+            file_name,
+            "backlog",
+            1 -> TaskState::Complete,
+        );
+
+        MoveTaskArgs {
+            from_group: "backlog".to_string(),
+            ids: vec![3, 1],
+            to_group: "sprint".to_string(),
+            position: Some(1),
+        }
+        .run(file_name)
+        .unwrap();
+        MoveNoteArgs {
+            from_group: "backlog".to_string(),
+            ids: vec![1],
+            to_group: "sprint".to_string(),
+            position: None,
+        }
+        .run(file_name)
+        .unwrap();
+
+        let Ok(mut data) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+        let completed_at = data.get_group("sprint").unwrap().tasks[0].completed_at;
+        clear_timestamps!(data, project);
+
+        common::clean(file_name);
+        assert!(completed_at.is_some());
+        assert_eq!(data, project);
+    }
+
+    #[test]
+    fn copy_items() {
+        let file_name = ".transfer-copy-items.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "backlog" -> [],
+            "sprint" -> [],
+        );
+        add_tasks!(file_name, "backlog", "task1", "task2");
+        add_notes!(file_name, "backlog", "note1");
+        complete_tasks!(
+            file_name,
+            "backlog",
+            2 -> TaskState::Complete,
+        );
+
+        CopyTaskArgs {
+            from_group: "backlog".to_string(),
+            ids: vec![2],
+            to_group: "sprint".to_string(),
+            position: None,
+        }
+        .run(file_name)
+        .unwrap();
+        CopyNoteArgs {
+            from_group: "backlog".to_string(),
+            ids: vec![1],
+            to_group: "backlog".to_string(),
+            position: Some(1),
+        }
+        .run(file_name)
+        .unwrap();
+
+        let Ok(data) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+        let backlog = data.get_group("backlog").unwrap();
+        let sprint = data.get_group("sprint").unwrap();
+
+        common::clean(file_name);
+        assert_eq!(backlog.tasks.len(), 2);
+        assert_eq!(sprint.tasks[0].id, 3);
+        assert_eq!(sprint.tasks[0].task, "task2");
+        assert_eq!(sprint.tasks[0].state, TaskState::Complete);
+        assert_eq!(sprint.tasks[0].completed_at, backlog.tasks[1].completed_at);
+        assert_eq!(backlog.notes[0].id, 2);
+        assert_eq!(backlog.notes[1].id, 1);
+        assert_eq!(data.last_task_id, 3);
+    }
+
+    #[test]
+    fn transfer_errors() {
+        let file_name = ".transfer-errors.pmgr";
+        common::clean(file_name);
+        commands::init::InitArgs.run(file_name).unwrap();
+
+        create_groups!(
+            file_name,
+            "backlog" -> [],
+            "sprint" -> [],
+        );
+        add_tasks!(file_name, "backlog", "task1");
+
+        let invalid_ids = MoveTaskArgs {
+            from_group: "backlog".to_string(),
+            ids: vec![1, 2, 3],
+            to_group: "sprint".to_string(),
+            position: None,
+        }
+        .run(file_name);
+        let missing_group = CopyTaskArgs {
+            from_group: "backlog".to_string(),
+            ids: vec![1],
+            to_group: "archive".to_string(),
+            position: None,
+        }
+        .run(file_name);
+        let bad_position = MoveTaskArgs {
+            from_group: "backlog".to_string(),
+            ids: vec![1],
+            to_group: "sprint".to_string(),
+            position: Some(2),
+        }
+        .run(file_name);
+
+        let Ok(data) = utils::get_data(file_name) else {
+            panic!("Failed to get project data");
+        };
+
+        common::clean(file_name);
+        assert!(matches!(invalid_ids, Err(Error::Missing(m)) if m.ends_with(": 2, 3")));
+        assert!(matches!(missing_group, Err(Error::Missing(_))));
+        assert!(matches!(bad_position, Err(Error::Usage(_))));
+        assert_eq!(data.get_group("backlog").unwrap().tasks.len(), 1);
+    }
+}